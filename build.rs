@@ -0,0 +1,96 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable with the input directory, as read by the binary at runtime.
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Sources whose content makes up the build id, so any change to the code or dependencies invalidates cached answers.
+/// `Cargo.lock` is not part of the repository and only counts once it exists.
+const BUILD_SOURCES: [&str; 3] = ["src", "Cargo.toml", "Cargo.lock"];
+
+/// Settings of the build that also make up the build id, as a debug and a release build solve at different speeds.
+const BUILD_SETTINGS: [&str; 4] = ["PROFILE", "OPT_LEVEL", "DEBUG", "TARGET"];
+
+/// Generates the table of embedded example inputs, which stays empty unless `embed-examples` is enabled,
+/// and sets `AOC_BUILD_ID` to a hash of the sources, the build settings and the enabled features.
+fn main() {
+    println!("cargo:rerun-if-env-changed={}", INPUT_DIR_VAR);
+
+    let mut sources = vec![];
+
+    for source in BUILD_SOURCES.into_iter().filter(|source| Path::new(source).exists()) {
+        println!("cargo:rerun-if-changed={}", source);
+        collect_files(Path::new(source), &mut sources);
+    }
+
+    sources.sort();
+
+    let build_id = sources.iter().fold(FNV_OFFSET, |hash, path| {
+        let hash = fnv(hash, path.to_string_lossy().as_bytes());
+        fnv(hash, &fs::read(path).unwrap())
+    });
+
+    let mut features = env::vars().map(|(name, _)| name).filter(|name| name.starts_with("CARGO_FEATURE_")).collect::<Vec<_>>();
+    features.sort();
+
+    let build_id = BUILD_SETTINGS.iter()
+        .map(|name| format!("{}={}", name, env::var(name).unwrap_or_default()))
+        .chain(features)
+        .fold(build_id, |hash, setting| fnv(hash, setting.as_bytes()));
+
+    println!("cargo:rustc-env=AOC_BUILD_ID={:016x}", build_id);
+
+    let mut entries = String::new();
+
+    if env::var_os("CARGO_FEATURE_EMBED_EXAMPLES").is_some() {
+        let input_dir = env::var(INPUT_DIR_VAR).unwrap_or(DEFAULT_INPUT_DIR.to_string());
+        let input_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join(input_dir);
+
+        println!("cargo:rerun-if-changed={}", input_dir.display());
+
+        match fs::read_dir(&input_dir) {
+            Ok(dir) => {
+                let mut files = dir
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .filter(|name| is_example(name))
+                    .collect::<Vec<_>>();
+
+                files.sort();
+
+                for file in files {
+                    entries.push_str(&format!("    ({:?}, include_str!({:?})),\n", file, input_dir.join(&file)));
+                }
+            }
+            Err(e) => println!("cargo:warning=no examples embedded, could not read {}: {}", input_dir.display(), e),
+        }
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out_path, format!("static EMBEDDED: &[(&str, &str)] = &[\n{}];\n", entries)).unwrap();
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).unwrap().filter_map(Result::ok) {
+            collect_files(&entry.path(), files);
+        }
+    } else {
+        files.push(path.to_path_buf());
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Example inputs are named `NN_test*.txt`, actual inputs `NN.txt` are never embedded.
+fn is_example(name: &str) -> bool {
+    let bytes = name.as_bytes();
+
+    bytes.len() > 2 && bytes[..2].iter().all(u8::is_ascii_digit) && name[2..].starts_with("_test") && name.ends_with(".txt")
+}
@@ -0,0 +1,129 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Counts the allocations of each thread once tracking is enabled, otherwise it only forwards to the system allocator.
+/// Only counts if a binary installs it as its global allocator.
+pub struct CountingAllocator;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+    static BYTES: Cell<usize> = const { Cell::new(0) };
+    /// Bytes allocated minus bytes freed by this thread, negative if it frees memory allocated elsewhere.
+    static LIVE: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn record_alloc(size: usize) {
+    let _ = COUNT.try_with(|count| count.set(count.get() + 1));
+    let _ = BYTES.try_with(|bytes| bytes.set(bytes.get() + size));
+
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + size as isize);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+fn record_dealloc(size: usize) {
+    let _ = LIVE.try_with(|live| live.set(live.get() - size as isize));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size());
+        }
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ENABLED.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+        }
+
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size());
+        }
+
+        System.alloc_zeroed(layout)
+    }
+
+    /// Counted as a new allocation of `new_size` bytes replacing the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if ENABLED.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Starts counting allocations. Without it the allocator adds no more than a flag check per allocation.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Allocations made by the current thread while running a closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: usize,
+    pub bytes: usize,
+    /// Highest number of bytes allocated and not yet freed at any point.
+    pub peak: usize,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocs {} peak {}", self.count, format_bytes(self.bytes), format_bytes(self.peak))
+    }
+}
+
+/// Runs `f` and returns the allocations it made on the current thread, if tracking is enabled.
+/// Allocations made by threads that `f` spawns are not included.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    COUNT.set(0);
+    BYTES.set(0);
+    LIVE.set(0);
+    PEAK.set(0);
+
+    let result = f();
+
+    let stats = AllocStats { count: COUNT.get(), bytes: BYTES.get(), peak: PEAK.get().max(0) as usize };
+
+    (result, Some(stats))
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit])
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::harness::{Context, Error};
+use crate::store;
+
+/// File in the input directory holding the answers.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known correct answers for the actual inputs, stored as a small TOML file with one `[dayNN]` table per day.
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        store::load(path, "answers")
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        store::save(self, path, "answers")
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+        let mut current_day = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let result = if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                parse_day(table.trim()).map(|day| current_day = Some(day))
+            } else {
+                parse_entry(line).and_then(|(part, answer)| {
+                    let day = current_day.context("answer outside of a [dayNN] table")?;
+                    answers.insert(day, part, answer);
+                    Ok(())
+                })
+            };
+
+            result.map_err(|e| e.at_line(idx + 1))?;
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut last_day = None;
+
+        for (&(day, part), answer) in &self.answers {
+            if last_day != Some(day) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{:0>2}]", day)?;
+                last_day = Some(day);
+            }
+
+            writeln!(f, "part{} = {}", part, format_value(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_day(table: &str) -> Result<u8, Error> {
+    table.strip_prefix("day")
+        .and_then(|day| day.parse::<u8>().ok())
+        .context(&format!("invalid table '{}', expected [dayNN]", table))
+}
+
+fn parse_entry(line: &str) -> Result<(u8, String), Error> {
+    let (key, value) = line.split_once('=').context("expected 'partN = answer'")?;
+
+    let part =
+        key.trim()
+            .strip_prefix("part")
+            .and_then(|part| part.parse::<u8>().ok())
+            .filter(|part| *part == 1 || *part == 2)
+            .context(&format!("invalid key '{}', expected part1 or part2", key.trim()))?;
+
+    Ok((part, parse_value(value.trim())?))
+}
+
+fn parse_value(value: &str) -> Result<String, Error> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return if value.parse::<i128>().is_ok() {
+            Ok(value.to_string())
+        } else {
+            Err(Error::new(format!("invalid value '{}', expected an integer or a string", value)))
+        };
+    };
+
+    let quoted = quoted.strip_suffix('"').context("unterminated string")?;
+
+    store::unescape(quoted, Some('"'))
+}
+
+fn format_value(answer: &str) -> String {
+    if answer.parse::<i128>().is_ok() {
+        return answer.to_string();
+    }
+
+    format!("\"{}\"", store::escape(answer, Some('"')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_strings() {
+        let answers = "[day01]\npart1 = 142\npart2 = -7\n\n# comment\n[day10]\npart1 = \"a \\\"b\\\"\\\\c\\nd\"\n".parse::<Answers>().unwrap();
+
+        assert_eq!(answers.get(1, 1), Some("142"));
+        assert_eq!(answers.get(1, 2), Some("-7"));
+        assert_eq!(answers.get(10, 1), Some("a \"b\"\\c\nd"));
+        assert_eq!(answers.get(10, 2), None);
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let mut answers = Answers::default();
+        answers.insert(3, 1, "4361".to_string());
+        answers.insert(3, 2, "-12".to_string());
+        answers.insert(13, 1, "#..#\n.##.".to_string());
+        answers.insert(14, 2, "say \"hi\" \\o/".to_string());
+
+        let parsed = answers.to_string().parse::<Answers>().unwrap();
+
+        assert_eq!(parsed.answers, answers.answers);
+    }
+
+    #[test]
+    fn rejects_malformed_values() {
+        for value in ["-", "--5", "1-2", "12a", "", "\"open", "\"a\" b", "\"\\x\""] {
+            assert!(format!("[day01]\npart1 = {}\n", value).parse::<Answers>().is_err(), "accepted '{}'", value);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_keys_and_tables() {
+        for s in ["part1 = 1", "[day01]\npart3 = 1", "[day01]\npart1 1", "[dayX]\npart1 = 1", "[week01]\npart1 = 1"] {
+            assert!(s.parse::<Answers>().is_err(), "accepted '{}'", s);
+        }
+    }
+
+    #[test]
+    fn reports_the_line_of_an_error() {
+        let error = "[day01]\npart1 = 1\npart2 = x\n".parse::<Answers>().unwrap_err();
+
+        assert!(error.to_string().contains("line 3"), "{}", error);
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::harness::Error;
+use crate::store;
+
+pub const DEFAULT_WARMUP: usize = 3;
+pub const DEFAULT_RUNS: usize = 20;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+
+        let n = samples.len();
+
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+
+        let variance =
+            samples.iter()
+                .map(|e| (e.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>() / n as f64;
+
+        // Nearest-rank percentile
+        let p95 = samples[((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1];
+
+        Some(Self {
+            runs: n,
+            min: samples[0],
+            median,
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Runs `f` `warmup` times without keeping the samples, then `runs` times keeping the duration each call reports,
+/// so that `f` can leave its setup out of the sample. Stops at the first error.
+pub fn measure<E, F: FnMut() -> Result<Duration, E>>(mut f: F, warmup: usize, runs: usize) -> Result<Stats, E> {
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    let samples = (0..runs.max(1)).map(|_| f()).collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Stats::from_samples(samples).unwrap())
+}
+
+pub fn print_header(comparing: bool) {
+    let change = if comparing { "change" } else { "" };
+    println!("{}", format!("{:<6} {:>4} {:>6} {:>12} {:>12} {:>12} {:>12} {:>12} {:>14}", "Day", "Part", "Runs", "min", "median", "mean", "p95", "stddev", change).trim_end().bold());
+}
+
+pub fn print_row(day: u8, part: u8, stats: &Option<Stats>, change: Option<Change>) {
+    let day = format!("Day{:0>2}", day);
+
+    let change = match change {
+        None => "".normal(),
+        Some(Change::Missing) => "no baseline".normal(),
+        Some(Change::InputChanged) => "input changed".yellow(),
+        Some(Change::Relative { percent, regressed: true }) => format!("{:+.1}%", percent).on_red(),
+        Some(Change::Relative { percent, .. }) if percent < 0.0 => format!("{:+.1}%", percent).green(),
+        Some(Change::Relative { percent, .. }) => format!("{:+.1}%", percent).normal(),
+    };
+
+    match stats {
+        Some(s) => println!(
+            "{}",
+            format!(
+                "{:<6} {:>4} {:>6} {:>12} {:>12} {:>12} {:>12} {:>12} {:>14}",
+                day.yellow(), part, s.runs,
+                format!("{:.2?}", s.min), format!("{:.2?}", s.median).purple(), format!("{:.2?}", s.mean), format!("{:.2?}", s.p95), format!("{:.2?}", s.stddev),
+                change,
+            ).trim_end()
+        ),
+        None => println!("{:<6} {:>4} {}", day.yellow(), part, "failed".on_red()),
+    }
+}
+
+/// File in the input directory holding the baseline, unless another one is given.
+pub const BASELINE_FILE: &str = "bench_baseline.txt";
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+#[derive(Debug)]
+pub struct DayBench {
+    pub input_hash: Option<u64>,
+    pub part1: Option<Stats>,
+    pub part2: Option<Stats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Missing,
+    InputChanged,
+    Relative { percent: f64, regressed: bool },
+}
+
+/// Median timings of an earlier benchmark run, keyed by day, part and a hash of the input they were measured on.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, u8), (u64, Duration)>,
+}
+
+impl Baseline {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        store::load(path, "baseline")
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        store::save(self, path, "baseline")
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, input_hash: u64, stats: &Stats) {
+        self.medians.insert((day, part), (input_hash, stats.median));
+    }
+
+    pub fn compare(&self, day: u8, part: u8, input_hash: u64, stats: &Stats, threshold: f64) -> Change {
+        match self.medians.get(&(day, part)) {
+            None => Change::Missing,
+            Some(&(hash, _)) if hash != input_hash => Change::InputChanged,
+            Some(&(_, median)) => {
+                let percent = (stats.median.as_secs_f64() / median.as_secs_f64() - 1.0) * 100.0;
+                Change::Relative { percent, regressed: percent > threshold }
+            }
+        }
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut baseline = Self::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+
+            let [day, part, hash, nanos] = fields[..] else {
+                return Err(Error::new("expected 'day part input_hash median_nanos'").at_line(idx + 1));
+            };
+
+            let parse = || -> Result<_, Error> {
+                let hash = u64::from_str_radix(hash, 16)?;
+                Ok(((day.parse()?, part.parse()?), (hash, Duration::from_nanos(nanos.parse()?))))
+            };
+
+            let (key, value) = parse().map_err(|e| e.at_line(idx + 1))?;
+            baseline.medians.insert(key, value);
+        }
+
+        Ok(baseline)
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day part input_hash median_nanos")?;
+
+        for (&(day, part), &(hash, median)) in &self.medians {
+            writeln!(f, "{:0>2} {} {:016x} {}", day, part, hash, median.as_nanos())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn stats_of_an_odd_number_of_samples() {
+        let stats = Stats::from_samples(millis(&[5, 1, 3, 2, 4])).unwrap();
+
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+    }
+
+    #[test]
+    fn stats_of_an_even_number_of_samples() {
+        let stats = Stats::from_samples(millis(&[4, 1, 3, 2])).unwrap();
+
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.p95, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn p95_is_the_nearest_rank() {
+        let samples = (1..=40).collect::<Vec<_>>();
+        let stats = Stats::from_samples(millis(&samples)).unwrap();
+
+        assert_eq!(stats.p95, Duration::from_millis(38));
+        assert_eq!(Stats::from_samples(millis(&[7])).unwrap().p95, Duration::from_millis(7));
+    }
+
+    #[test]
+    fn no_stats_without_samples() {
+        assert!(Stats::from_samples(vec![]).is_none());
+    }
+
+    #[test]
+    fn baseline_round_trips_through_display() {
+        let mut baseline = Baseline::default();
+        baseline.insert(7, 2, 0xdead_beef, &Stats::from_samples(millis(&[12])).unwrap());
+
+        let parsed = baseline.to_string().parse::<Baseline>().unwrap();
+
+        assert_eq!(parsed.medians, baseline.medians);
+    }
+}
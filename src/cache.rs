@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::harness::Error;
+use crate::store;
+
+/// File in the input directory holding the cached answers.
+pub const CACHE_FILE: &str = "answer_cache.txt";
+
+/// Identifies the code that computed an answer, a hash of the sources taken when building.
+pub const BUILD_ID: &str = env!("AOC_BUILD_ID");
+
+/// Answer and time of a part on the actual input in an earlier run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedAnswer {
+    pub answer: String,
+    pub duration: Duration,
+}
+
+/// Answers of earlier runs, keyed by day, part, a hash of the input and its parameters, and the build.
+/// Entries of other builds are dropped when loading, so the cache only ever holds answers of the current code.
+#[derive(Debug, Default)]
+pub struct AnswerCache {
+    entries: BTreeMap<(u8, u8, u64), CachedAnswer>,
+}
+
+impl AnswerCache {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        store::load(path, "answer cache")
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        store::save(self, path, "answer cache")
+    }
+
+    pub fn get(&self, day: u8, part: u8, input_hash: u64) -> Option<&CachedAnswer> {
+        self.entries.get(&(day, part, input_hash))
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, input_hash: u64, answer: CachedAnswer) {
+        self.entries.insert((day, part, input_hash), answer);
+    }
+}
+
+impl FromStr for AnswerCache {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cache = Self::default();
+
+        for (idx, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parse = || -> Result<_, Error> {
+                let fields = line.splitn(6, ' ').collect::<Vec<_>>();
+
+                let [build, day, part, hash, nanos, answer] = fields[..] else {
+                    return Err(Error::new("expected 'build day part input_hash nanos answer'"));
+                };
+
+                let key = (day.parse()?, part.parse()?, u64::from_str_radix(hash, 16)?);
+                let answer = CachedAnswer { answer: store::unescape(answer, None)?, duration: Duration::from_nanos(nanos.parse()?) };
+
+                Ok((build == BUILD_ID).then_some((key, answer)))
+            };
+
+            if let Some((key, answer)) = parse().map_err(|e| e.at_line(idx + 1))? {
+                cache.entries.insert(key, answer);
+            }
+        }
+
+        Ok(cache)
+    }
+}
+
+impl Display for AnswerCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# build day part input_hash nanos answer")?;
+
+        for (&(day, part, hash), cached) in &self.entries {
+            writeln!(f, "{} {:0>2} {} {:016x} {} {}", BUILD_ID, day, part, hash, cached.duration.as_nanos(), store::escape(&cached.answer, None))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_display() {
+        let mut cache = AnswerCache::default();
+        cache.insert(21, 2, 0x1234, CachedAnswer { answer: "607334325965751".to_string(), duration: Duration::from_micros(1500) });
+        cache.insert(10, 1, u64::MAX, CachedAnswer { answer: "a b\n c".to_string(), duration: Duration::ZERO });
+
+        let parsed = cache.to_string().parse::<AnswerCache>().unwrap();
+
+        assert_eq!(parsed.entries, cache.entries);
+    }
+
+    #[test]
+    fn drops_entries_of_other_builds() {
+        let s = format!("{} 01 1 00000000000000ff 10 142\nother 01 2 00000000000000ff 10 281\n", BUILD_ID);
+        let cache = s.parse::<AnswerCache>().unwrap();
+
+        assert_eq!(cache.get(1, 1, 0xff).map(|cached| cached.answer.as_str()), Some("142"));
+        assert_eq!(cache.get(1, 2, 0xff), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for line in ["x 01 1 ff 10", "x 01 one ff 10 1", "x 01 1 zz 10 1", "x 01 1 ff -1 1"] {
+            assert!(line.parse::<AnswerCache>().is_err(), "accepted '{}'", line);
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use std::env;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::bench;
+use crate::bench::BASELINE_FILE;
+use crate::harness::{Context, DEFAULT_INPUT_DIR, DEFAULT_TIMEOUT, Error, Mode, Params, RunOptions, STDIN_PATH};
+
+pub const USAGE: &str = "\
+Usage: aoc-2023 [DAYS] [OPTIONS]
+       aoc-2023 bench [DAYS] [BENCH OPTIONS]
+       aoc-2023 watch [DAY] [OPTIONS]
+       aoc-2023 new <DAY> [--title <title>]
+
+Every command takes --input-dir <dir>, the directory with the inputs, answers and baseline.
+It defaults to $AOC_INPUT_DIR, or `input` if that is not set. A binary built with
+`--features embed-examples` carries the examples of that directory, used where it lacks them.
+
+DAYS is `all`, a list of days and ranges like `3,5,10-14`, or empty for the latest day.
+`watch` runs a single day again whenever its input or test files change, and takes the options
+up to --param.
+
+Options:
+  --part <1|2>         Only run one part
+  --test-only          Only run the example inputs
+  --actual-only        Only run the actual input
+  --input <path>       Read the actual input from <path>, or from stdin for `-`, requires a single day.
+                       Its answers are not checked against the recorded ones
+  --timeout <secs>     Give up on a part after <secs>, defaults to 30
+  --variants           Also run the alternative implementations of each part and compare them
+  --param <name=value> Override a parameter of the actual input, such as steps for day 21, repeatable.
+                       The answers are not checked against the recorded ones
+  --jobs <n>           Run <n> days side by side, defaults to one per core. Days running side by side
+                       slow each other down, so use --jobs 1 when comparing timings. A run of several
+                       days ends with its wall-clock time, and on Linux its CPU time in 10ms steps
+  --alloc              Count allocations, bytes allocated and peak live bytes per part
+  --record             Save the actual answers as known correct answers
+  --cache              Reuse the answers of earlier runs of the same build on the same inputs,
+                       kept in answer_cache.txt in the input directory
+  --fresh              Solve every part again and refresh the cached answers, implies --cache
+  --json <path>        Write a JSON report to <path>
+  --junit <path>       Write a JUnit XML report to <path>
+  --markdown <path>    Write the summary table to <path> as Markdown, `all` prints the summary anyway
+  --plot <path>        Where `all` writes the timing chart, defaults to result.svg
+
+Bench options:
+  --warmup <n>         Unmeasured runs per part
+  --runs <n>           Measured runs per part
+  --save-baseline      Save the medians as the new baseline
+  --compare            Compare the medians against the baseline
+  --threshold <pct>    Slowdown in percent that counts as a regression
+  --baseline <path>    Baseline file to use, defaults to bench_baseline.txt in the input directory
+  --input <path>       Benchmark on the input at <path>, or on stdin for `-`, requires a single day
+  --param <name=value> Override a parameter of the actual input, repeatable.
+                       Neither can be combined with --save-baseline or --compare
+
+New options:
+  --title <title>      Title of the puzzle, can be filled in later";
+
+const DEFAULT_PLOT_PATH: &str = "result.svg";
+
+/// Environment variable with the input directory, used unless --input-dir is given.
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    Latest,
+    All,
+    Days(Vec<u8>),
+}
+
+impl FromStr for Selection {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+
+        let mut days = vec![];
+
+        for item in s.split(',') {
+            let day = |s: &str| -> Result<u8, Error> {
+                s.parse::<u8>()
+                    .ok()
+                    .filter(|day| (1..=25).contains(day))
+                    .context(&format!("invalid day '{}' in '{}', expected a number from 1 to 25", s, item))
+            };
+
+            match item.split_once('-') {
+                Some((from, to)) => {
+                    let (from, to) = (day(from)?, day(to)?);
+
+                    if from > to {
+                        return Err(Error::new(format!("invalid range '{}', {} is after {}", item, from, to)));
+                    }
+
+                    days.extend(from..=to);
+                }
+                None => days.push(day(item)?),
+            }
+        }
+
+        days.sort();
+        days.dedup();
+
+        Ok(Self::Days(days))
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchOptions {
+    pub save_baseline: bool,
+    pub compare: bool,
+    pub warmup: usize,
+    pub runs: usize,
+    pub threshold: f64,
+    pub baseline: String,
+    /// Overrides the path of the actual input.
+    pub input: Option<String>,
+    /// Overrides parameters of the actual input.
+    pub params: Params,
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Run {
+        options: RunOptions,
+        alloc: bool,
+        record: bool,
+        /// How many days to run side by side, one per core unless given.
+        jobs: Option<NonZeroUsize>,
+        /// Reuse and store answers in the answer cache.
+        cache: bool,
+        /// Solve every part again, refreshing the answer cache.
+        fresh: bool,
+        json: Option<String>,
+        junit: Option<String>,
+        /// Where to write the summary table as Markdown.
+        markdown: Option<String>,
+        plot: String,
+    },
+    Bench(BenchOptions),
+    Watch(RunOptions),
+    /// Generates the module and example inputs of a day that has no solution yet.
+    New {
+        day: u8,
+        title: String,
+    },
+}
+
+#[derive(Debug)]
+pub struct Cli {
+    pub selection: Selection,
+    pub command: Command,
+    pub input_dir: String,
+}
+
+impl Cli {
+    /// Parses the arguments without the program name.
+    pub fn parse(mut args: Vec<String>) -> Result<Self, Error> {
+        let input_dir = take_option(&mut args, "--input-dir")?
+            .or_else(|| env::var(INPUT_DIR_VAR).ok().filter(|dir| !dir.is_empty()))
+            .unwrap_or(DEFAULT_INPUT_DIR.to_string());
+
+        if args.first().is_some_and(|a| a == "new") {
+            return Self::parse_new(args.split_off(1), input_dir);
+        }
+
+        let is_bench = args.first().is_some_and(|a| a == "bench");
+        let is_watch = args.first().is_some_and(|a| a == "watch");
+
+        if is_bench || is_watch {
+            args.remove(0);
+        }
+
+        let command = if is_watch {
+            Command::Watch(parse_run_options(&mut args)?)
+        } else if is_bench {
+            Command::Bench(BenchOptions {
+                save_baseline: take_flag(&mut args, "--save-baseline"),
+                compare: take_flag(&mut args, "--compare"),
+                warmup: take_option(&mut args, "--warmup")?.unwrap_or(bench::DEFAULT_WARMUP),
+                runs: take_option(&mut args, "--runs")?.unwrap_or(bench::DEFAULT_RUNS),
+                threshold: take_option(&mut args, "--threshold")?.unwrap_or(bench::DEFAULT_THRESHOLD),
+                baseline: take_option(&mut args, "--baseline")?.unwrap_or(Path::new(&input_dir).join(BASELINE_FILE).to_string_lossy().into_owned()),
+                input: take_option(&mut args, "--input")?,
+                params: take_params(&mut args)?,
+            })
+        } else {
+            Command::Run {
+                options: parse_run_options(&mut args)?,
+                alloc: take_flag(&mut args, "--alloc"),
+                record: take_flag(&mut args, "--record"),
+                jobs: take_option(&mut args, "--jobs")?,
+                cache: take_flag(&mut args, "--cache"),
+                fresh: take_flag(&mut args, "--fresh"),
+                json: take_option(&mut args, "--json")?,
+                junit: take_option(&mut args, "--junit")?,
+                markdown: take_option(&mut args, "--markdown")?,
+                plot: take_option(&mut args, "--plot")?.unwrap_or(DEFAULT_PLOT_PATH.to_string()),
+            }
+        };
+
+        let selection = match args.first() {
+            Some(arg) if !arg.starts_with("--") => args.remove(0).parse()?,
+            _ => Selection::Latest,
+        };
+
+        if let Some(arg) = args.first() {
+            return Err(Error::new(format!("unknown argument '{}'", arg)));
+        }
+
+        let single_day = matches!(&selection, Selection::Latest) || matches!(&selection, Selection::Days(days) if days.len() == 1);
+
+        match &command {
+            Command::Watch(_) if !single_day => return Err(Error::new("watch requires a single day")),
+            Command::Watch(RunOptions { input: Some(path), .. }) if path == STDIN_PATH => return Err(Error::new("watch can't read the input from stdin")),
+            Command::Run { options: RunOptions { input: Some(_), .. }, .. }
+            | Command::Watch(RunOptions { input: Some(_), .. })
+            | Command::Bench(BenchOptions { input: Some(_), .. }) if !single_day => {
+                return Err(Error::new("--input requires a single day"));
+            }
+            Command::Run { options, record: true, .. } if !options.uses_recorded_inputs() => {
+                return Err(Error::new("--record can't be combined with --input or --param, the recorded answers are for the actual inputs"));
+            }
+            Command::Bench(options) if (options.save_baseline || options.compare) && (options.input.is_some() || !options.params.is_empty()) => {
+                return Err(Error::new("--save-baseline and --compare can't be combined with --input or --param, the baseline is for the actual inputs"));
+            }
+            _ => {}
+        }
+
+        Ok(Self { selection, command, input_dir })
+    }
+
+    fn parse_new(mut args: Vec<String>, input_dir: String) -> Result<Self, Error> {
+        let title = take_option(&mut args, "--title")?.unwrap_or("TODO".to_string());
+
+        let day = match args.first() {
+            Some(arg) if !arg.starts_with("--") => args.remove(0),
+            _ => return Err(Error::new("missing day for new")),
+        };
+
+        let day = day.parse::<u8>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .context(&format!("invalid day '{}', expected a number from 1 to 25", day))?;
+
+        if let Some(arg) = args.first() {
+            return Err(Error::new(format!("unknown argument '{}'", arg)));
+        }
+
+        Ok(Self { selection: Selection::Days(vec![day]), command: Command::New { day, title }, input_dir })
+    }
+}
+
+fn parse_run_options(args: &mut Vec<String>) -> Result<RunOptions, Error> {
+    let test_only = take_flag(args, "--test-only");
+    let actual_only = take_flag(args, "--actual-only");
+
+    let mode = match (test_only, actual_only) {
+        (true, true) => return Err(Error::new("--test-only and --actual-only can't be combined")),
+        (true, false) => Mode::TestOnly,
+        (false, true) => Mode::ActualOnly,
+        (false, false) => Mode::All,
+    };
+
+    let part = take_option::<u8>(args, "--part")?;
+
+    if let Some(part) = part.filter(|part| *part != 1 && *part != 2) {
+        return Err(Error::new(format!("invalid part '{}', expected 1 or 2", part)));
+    }
+
+    Ok(RunOptions {
+        part,
+        mode,
+        input: take_option(args, "--input")?,
+        timeout: match take_option::<f64>(args, "--timeout")? {
+            Some(seconds) if seconds > 0.0 && seconds.is_finite() => Duration::from_secs_f64(seconds),
+            Some(seconds) => return Err(Error::new(format!("invalid timeout '{}', expected a positive number of seconds", seconds))),
+            None => DEFAULT_TIMEOUT,
+        },
+        variants: take_flag(args, "--variants"),
+        params: take_params(args)?,
+        cache: None,
+        fresh: false,
+    })
+}
+
+/// Takes every `--param name=value`, a later value replacing an earlier one of the same name.
+fn take_params(args: &mut Vec<String>) -> Result<Params, Error> {
+    let mut params = Params::default();
+
+    while let Some(param) = take_option::<String>(args, "--param")? {
+        let (name, value) = param.split_once('=')
+            .filter(|(name, _)| !name.is_empty())
+            .context(&format!("invalid parameter '{}', expected name=value", param))?;
+
+        params = params.with(name, value);
+    }
+
+    Ok(params)
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+    args.retain(|a| a != name);
+    args.len() != len
+}
+
+fn take_option<T: FromStr>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, Error> {
+    let Some(idx) = args.iter().position(|a| a == name) else {
+        return Ok(None);
+    };
+
+    args.remove(idx);
+
+    if idx >= args.len() {
+        return Err(Error::new(format!("missing value for {}", name)));
+    }
+
+    let value = args.remove(idx);
+
+    value.parse()
+        .map(Some)
+        .map_err(|_| Error::new(format!("invalid value for {}: '{}'", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(s: &str) -> Vec<u8> {
+        match s.parse::<Selection>().unwrap() {
+            Selection::Days(days) => days,
+            selection => panic!("expected days, got {:?}", selection),
+        }
+    }
+
+    #[test]
+    fn parses_lists_and_ranges() {
+        assert_eq!(days("7"), vec![7]);
+        assert_eq!(days("3,5,10-14"), vec![3, 5, 10, 11, 12, 13, 14]);
+        assert_eq!(days("1-25").len(), 25);
+        assert_eq!(days("4-4"), vec![4]);
+    }
+
+    #[test]
+    fn sorts_and_deduplicates_days() {
+        assert_eq!(days("12,2-4,3,1"), vec![1, 2, 3, 4, 12]);
+    }
+
+    #[test]
+    fn parses_all() {
+        assert_eq!("all".parse::<Selection>().unwrap(), Selection::All);
+    }
+
+    #[test]
+    fn rejects_invalid_days_and_ranges() {
+        for s in ["0", "26", "14-10", "1-26", "0-3", "x", "", "3,", "1--3", "-3", "3-", "all,1"] {
+            assert!(s.parse::<Selection>().is_err(), "accepted '{}'", s);
+        }
+    }
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_run_options() {
+        let cli = Cli::parse(args("3-5 --part 2 --test-only --timeout 1.5 --param steps=6 --param steps=10")).unwrap();
+
+        assert_eq!(cli.selection, Selection::Days(vec![3, 4, 5]));
+
+        let Command::Run { options, .. } = cli.command else {
+            panic!("expected a run, got {:?}", cli.command);
+        };
+
+        assert_eq!(options.part, Some(2));
+        assert_eq!(options.mode, Mode::TestOnly);
+        assert_eq!(options.timeout, Duration::from_millis(1500));
+        assert_eq!(options.params, Params::default().with("steps", 10));
+    }
+
+    #[test]
+    fn parses_jobs() {
+        let Command::Run { jobs, .. } = Cli::parse(args("all --jobs 1")).unwrap().command else {
+            panic!("expected a run");
+        };
+
+        assert_eq!(jobs, NonZeroUsize::new(1));
+    }
+
+    #[test]
+    fn parses_bench_input_and_params() {
+        let Command::Bench(options) = Cli::parse(args("bench 21 --input - --param steps=64")).unwrap().command else {
+            panic!("expected a bench");
+        };
+
+        assert_eq!(options.input.as_deref(), Some(STDIN_PATH));
+        assert_eq!(options.params, Params::default().with("steps", 64));
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        for s in ["--part 3", "--test-only --actual-only", "1 --bogus", "--timeout 0", "--timeout", "--param steps", "1,2 --input x.txt", "watch all", "1 --input x.txt --record", "1 --input - --record", "21 --param steps=10 --record", "--jobs 0", "watch --jobs 2", "bench 1,2 --input x.txt", "bench 1 --input x.txt --save-baseline", "bench 21 --param steps=10 --compare"] {
+            assert!(Cli::parse(args(s)).is_err(), "accepted '{}'", s);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! The commands of the command line: running, benchmarking, watching and scaffolding days.
+//! The binary only parses the arguments and turns the outcome into an exit code.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use colored::Colorize;
+
+use crate::{alloc, bench, harness, plot, registry, scaffold, watch, worker};
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::bench::{Baseline, Change};
+use crate::cache::{AnswerCache, CACHE_FILE};
+use crate::cli::{BenchOptions, Cli, Command, Selection};
+use crate::harness::{DayRunner, Error, Registration, Registry, Reporter};
+use crate::report::{JsonReporter, JunitReporter, PrettyReporter, Reporters, SummaryReporter};
+
+/// Whether everything a command checked turned out fine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    /// A part failed, regressed or disagreed with a variant, or a benchmark regressed.
+    Failed,
+}
+
+/// Runs the command, returning an error if it could not run at all, such as for an unknown day or an unreadable file.
+pub fn execute(cli: Cli) -> Result<Outcome, Error> {
+    let registry = registry();
+
+    harness::set_input_dir(&cli.input_dir);
+
+    let registrations = select(&registry, &cli.selection)?;
+
+    match cli.command {
+        Command::New { day, title } => new_day(&registry, day, &title),
+        Command::Watch(options) => match registrations.first() {
+            Some(registration) => watch::watch(*registration, &options),
+            None => Ok(Outcome::Passed),
+        },
+        Command::Bench(options) => run_bench(&registrations.iter().map(|r| r.load()).collect::<Vec<_>>(), &options),
+        Command::Run { mut options, alloc, record, jobs, cache, fresh, json, junit, markdown, plot } => {
+            if alloc {
+                alloc::enable();
+            }
+
+            if cache || fresh {
+                options.cache = Some(Arc::new(Mutex::new(AnswerCache::load(&harness::input_path(CACHE_FILE))?)));
+                options.fresh = fresh;
+            }
+
+            let answers_path = harness::input_path(ANSWERS_FILE);
+            let mut answers = Answers::load(&answers_path)?;
+
+            let mut reporters = Reporters(vec![Box::<PrettyReporter>::default()]);
+
+            if let Some(path) = json {
+                reporters.0.push(Box::new(JsonReporter::new(&path)));
+            }
+
+            if let Some(path) = junit {
+                reporters.0.push(Box::new(JunitReporter::new(&path)));
+            }
+
+            if cli.selection == Selection::All || markdown.is_some() {
+                reporters.0.push(Box::new(SummaryReporter::new(markdown.as_deref())));
+            }
+
+            let run = worker::run_parallel(&registrations, &answers, &options, jobs, &mut reporters);
+
+            match run.cpu_time_display() {
+                Some(cpu_time) if run.results.len() > 1 => println!("Ran {} days in {:?} wall-clock, {} CPU time", run.results.len(), run.wall_time, cpu_time),
+                None if run.results.len() > 1 => println!("Ran {} days in {:?} wall-clock", run.results.len(), run.wall_time),
+                _ => {}
+            }
+
+            let results = run.results;
+
+            reporters.finish()?;
+
+            if cli.selection == Selection::All {
+                write_plot(&registry, &results, &plot);
+            }
+
+            if let Some(cache) = &options.cache {
+                if let Err(e) = cache.lock().unwrap().save(&harness::input_path(CACHE_FILE)) {
+                    eprintln!("{}", e);
+                }
+            }
+
+            let parts = || results.iter().flat_map(|(_, parts)| parts).map(|(_, result)| result);
+            let mut outcome = Outcome::Passed;
+
+            if record {
+                for (id, parts) in &results {
+                    for (part, result) in parts {
+                        if let Some(answer) = &result.answer {
+                            answers.insert(*id, *part, answer.clone());
+                        }
+                    }
+                }
+
+                answers.save(&answers_path)?;
+                println!("Answers recorded to {}", answers_path);
+            } else if parts().any(|result| result.regressed()) {
+                eprintln!("{}", "Some answers regressed".red());
+                outcome = Outcome::Failed;
+            }
+
+            if parts().any(|result| result.variants_disagree()) {
+                eprintln!("{}", "Some variants disagree with their part".red());
+                outcome = Outcome::Failed;
+            }
+
+            if parts().any(|result| result.failed()) {
+                eprintln!("{}", "Some parts failed".red());
+                outcome = Outcome::Failed;
+            }
+
+            Ok(outcome)
+        }
+    }
+}
+
+/// The registered days the selection names, failing on the first day that is not registered.
+fn select(registry: &Registry, selection: &Selection) -> Result<Vec<Registration>, Error> {
+    match selection {
+        Selection::Latest => Ok(registry.latest().into_iter().copied().collect()),
+        Selection::All => Ok(registry.all().copied().collect()),
+        Selection::Days(ids) => ids.iter().map(|id| registry.get(*id).copied()).collect(),
+    }
+}
+
+/// Writes the timing chart of a run of every day, with a gap for each day or part that has no time.
+fn write_plot(registry: &Registry, results: &[(u8, Vec<(u8, harness::PartResult)>)], path: &str) {
+    let duration = |id: u8, part: u8| {
+        results.iter()
+            .find(|(day, _)| *day == id)
+            .and_then(|(_, parts)| parts.iter().find(|(p, _)| *p == part))
+            .and_then(|(_, result)| result.duration)
+    };
+
+    let (p1, p2): (Vec<_>, Vec<_>) = (1..=registry.latest().map(|r| r.id).unwrap_or(0)).map(|id| (duration(id, 1), duration(id, 2))).unzip();
+
+    match plot::plot(Path::new(path), &p1, &p2) {
+        Ok(_) => println!("Timing chart written to {}", path),
+        Err(e) => eprintln!("Failed to write timing chart to {}: {}", path, e),
+    }
+}
+
+fn new_day(registry: &Registry, day: u8, title: &str) -> Result<Outcome, Error> {
+    if registry.get(day).is_ok() {
+        return Err(Error::new(format!("Day{:0>2} is already registered, refusing to overwrite it", day)));
+    }
+
+    scaffold::new_day(day, title)?.iter().for_each(|path| println!("Wrote {}", path));
+    println!("Day{:0>2} is registered and runs after the next build", day);
+
+    Ok(Outcome::Passed)
+}
+
+fn run_bench(selected: &[DayRunner], options: &BenchOptions) -> Result<Outcome, Error> {
+    let mut baseline = Baseline::load(&options.baseline)?;
+
+    println!("Benchmarking with {} warmup and {} measured runs per part", options.warmup, options.runs);
+    bench::print_header(options.compare);
+
+    let mut any_regressed = false;
+
+    for day in selected {
+        let result = (day.bench)(options.input.as_deref(), &options.params, options.warmup, options.runs);
+
+        for (part, stats) in [(1, result.part1), (2, result.part2)] {
+            let change =
+                stats.as_ref()
+                    .zip(result.input_hash)
+                    .filter(|_| options.compare)
+                    .map(|(stats, hash)| baseline.compare(day.id, part, hash, stats, options.threshold));
+
+            any_regressed |= matches!(change, Some(Change::Relative { regressed: true, .. }));
+
+            bench::print_row(day.id, part, &stats, change);
+
+            if let (true, Some(stats), Some(hash)) = (options.save_baseline, &stats, result.input_hash) {
+                baseline.insert(day.id, part, hash, stats);
+            }
+        }
+    }
+
+    if options.save_baseline {
+        baseline.save(&options.baseline)?;
+        println!("Baseline saved to {}", options.baseline);
+    }
+
+    if any_regressed {
+        eprintln!("{}", format!("Some parts regressed by more than {}%", options.threshold).red());
+        return Ok(Outcome::Failed);
+    }
+
+    Ok(Outcome::Passed)
+}
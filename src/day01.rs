@@ -1,6 +1,8 @@
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day01() -> Day<i32, i32> {
+pub const REGISTRATION: Registration = Registration::new(1, "Trebuchet?!", || day01().f());
+
+fn day01() -> Day<i32, i32> {
     Day::new(1, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -11,7 +13,7 @@ impl Part<i32> for Part1 {
         142
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
+    fn solve(&self, input: &[String]) -> Result<i32, Error> {
         solve(input, &(1..=9).map(|i| i.to_string()).collect::<Vec<_>>())
     }
 }
@@ -23,7 +25,7 @@ impl Part<i32> for Part2 {
         281
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
+    fn solve(&self, input: &[String]) -> Result<i32, Error> {
         solve(input, &DIGITS.into_iter().map(|s| s.to_string()).collect::<Vec<_>>())
     }
 }
@@ -50,28 +52,26 @@ const DIGITS: [&str; 18] = [
 ];
 
 
-fn solve(input: &[String], digits: &[String]) -> i32 {
-    input
-        .iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| {
-            let min = digits.iter()
-                .enumerate()
-                .map(|(idx, s)| (idx, l.find(s)))
-                .filter_map(|(idx, str_idx)| str_idx.map(|str_idx| (idx, str_idx)))
-                .min_by_key(|&(_, str_idx)| str_idx)
-                .unwrap()
-                .0;
+fn solve(input: &[String], digits: &[String]) -> Result<i32, Error> {
+    let values = parse_lines(input, |l| {
+        let min = digits.iter()
+            .enumerate()
+            .map(|(idx, s)| (idx, l.find(s)))
+            .filter_map(|(idx, str_idx)| str_idx.map(|str_idx| (idx, str_idx)))
+            .min_by_key(|&(_, str_idx)| str_idx)
+            .context("line contains no digit")?
+            .0;
+
+        let max = digits.iter()
+            .enumerate()
+            .map(|(idx, s)| (idx, l.rfind(s)))
+            .filter_map(|(idx, str_idx)| str_idx.map(|str_idx| (idx, str_idx)))
+            .max_by_key(|&(_, str_idx)| str_idx)
+            .context("line contains no digit")?
+            .0;
 
-            let max = digits.iter()
-                .enumerate()
-                .map(|(idx, s)| (idx, l.rfind(s)))
-                .filter_map(|(idx, str_idx)| str_idx.map(|str_idx| (idx, str_idx)))
-                .max_by_key(|&(_, str_idx)| str_idx)
-                .unwrap()
-                .0;
+        Ok(((min % 9) + 1) * 10 + (max % 9) + 1)
+    })?;
 
-            ((min % 9) + 1) * 10 + (max % 9) + 1
-        })
-        .sum::<usize>() as i32
+    Ok(values.into_iter().sum::<usize>() as i32)
 }
@@ -2,42 +2,54 @@ use std::cmp::max;
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, param, Params, parse_lines, Part, Registration};
 
-pub fn day02() -> Day<i32, i32> {
-    Day::new(2, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(2, "Cube Conundrum", || day02().f());
+
+fn day02() -> Day<i32, i32, Vec<Game>> {
+    Day::with_parser(2, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
+impl Part<i32, Vec<Game>> for Part1 {
     fn expect_test(&self) -> i32 {
         8
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        parse(input).iter()
+    /// How many cubes of each color the bag contains.
+    fn params(&self) -> Params {
+        Params::default()
+            .with("red", 12)
+            .with("green", 13)
+            .with("blue", 14)
+    }
+
+    fn solve(&self, games: &Vec<Game>) -> Result<i32, Error> {
+        let (red, green, blue) = (param("red")?, param("green")?, param("blue")?);
+
+        Ok(games.iter()
             .filter(|g|
                 g.rounds.iter()
-                    .all(|r| r.red <= 12 && r.green <= 13 && r.blue <= 14)
+                    .all(|r| r.red <= red && r.green <= green && r.blue <= blue)
             )
             .map(|g| g.id as i32)
-            .sum()
+            .sum())
     }
 }
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl Part<i32, Vec<Game>> for Part2 {
     fn expect_test(&self) -> i32 {
         2286
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        parse(input)
-            .into_iter()
+    fn solve(&self, games: &Vec<Game>) -> Result<i32, Error> {
+        Ok(games.iter()
             .map(|g|
-                g.rounds.into_iter()
+                g.rounds.iter()
+                    .copied()
                     .reduce(|r1, r2|
                         Round::new(
                             max(r1.red, r2.red),
@@ -48,7 +60,7 @@ impl Part<i32> for Part2 {
                     .unwrap()
             )
             .map(|min| (min.red * min.green * min.blue) as i32)
-            .sum()
+            .sum())
     }
 }
 
@@ -66,15 +78,17 @@ impl Round {
 }
 
 impl FromStr for Round {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let map = s
             .split(',')
             .map(|s| s.trim())
-            .map(|e| e.split(' ').collect::<Vec<_>>())
-            .map(|e| (e[1], e[0].parse::<usize>().unwrap()))
-            .collect::<HashMap<_, _>>();
+            .map(|e| {
+                let (count, color) = e.split_once(' ').context(&format!("invalid cube count '{}'", e))?;
+                Ok((color, count.parse::<usize>()?))
+            })
+            .collect::<Result<HashMap<_, _>, Error>>()?;
 
         Ok(
             Round::new(
@@ -99,20 +113,16 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let option = s.split_once(':').unwrap();
-        let id = option.0.split(' ').last().unwrap().parse::<usize>().unwrap();
-        let rounds = option.1.split(';').map(Round::from_str).collect::<Result<Vec<_>, ()>>().unwrap();
+        let option = s.split_once(':').context("missing ':' after game id")?;
+        let id = option.0.split(' ').next_back().context("missing game id")?.parse::<usize>()?;
+        let rounds = option.1.split(';').map(Round::from_str).collect::<Result<Vec<_>, _>>()?;
         Ok(Game::new(id, rounds))
     }
 }
 
-fn parse(input: &[String]) -> Vec<Game> {
-    input.iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| Game::from_str(l))
-        .collect::<Result<Vec<Game>, ()>>()
-        .unwrap()
+fn parse(input: &[String]) -> Result<Vec<Game>, Error> {
+    parse_lines(input, Game::from_str)
 }
@@ -1,40 +1,41 @@
 use std::collections::{HashMap, HashSet};
 use std::ops::{Add, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, Part, Registration};
 
-pub fn day03() -> Day<i32, i32> {
-    Day::new(3, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(3, "Gear Ratios", || day03().f());
+
+fn day03() -> Day<i32, i32, Map> {
+    Day::with_parser(3, |input| Ok(Map::from(input)), Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
+impl Part<i32, Map> for Part1 {
     fn expect_test(&self) -> i32 {
         4361
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        Map::from(input)
-            .get_parts_and_nonparts()
+    fn solve(&self, map: &Map) -> Result<i32, Error> {
+        Ok(map.get_parts_and_nonparts()
             .iter()
             .filter(|p| p.has_parts())
             .map(|p| p.number)
-            .sum()
+            .sum())
     }
 }
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl Part<i32, Map> for Part2 {
     fn expect_test(&self) -> i32 {
         467835
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let parts = Map::from(input).get_parts_and_nonparts();
+    fn solve(&self, map: &Map) -> Result<i32, Error> {
+        let parts = map.get_parts_and_nonparts();
 
-        parts.iter()
+        Ok(parts.iter()
             .flat_map(|p|
                 p.parts.iter()
                     .clone()
@@ -50,7 +51,7 @@ impl Part<i32> for Part2 {
             )
             .filter(|parts| parts.len() == 2)
             .map(|parts| parts[0].number * parts[1].number)
-            .sum()
+            .sum())
     }
 }
 
@@ -77,8 +78,7 @@ impl Map {
                 let cur_pos = p(x as i32, y as i32);
                 let possible_cur = self.raw.get(&cur_pos);
 
-                if possible_cur.is_some() && possible_cur.unwrap().is_numeric() {
-                    let cur = *possible_cur.unwrap();
+                if let Some(&cur) = possible_cur.filter(|c| c.is_numeric()) {
                     current_number.push(cur);
 
                     for d in Vec2::DIRECTIONS {
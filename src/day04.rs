@@ -2,34 +2,34 @@ use std::cmp::min;
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day04() -> Day<u32, u32> {
-    Day::new(4, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(4, "Scratchcards", || day04().f());
+
+fn day04() -> Day<u32, u32, Vec<ScratchCard>> {
+    Day::with_parser(4, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u32> for Part1 {
+impl Part<u32, Vec<ScratchCard>> for Part1 {
     fn expect_test(&self) -> u32 {
         13
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
-        parse(input).iter().map(|e| e.score()).sum()
+    fn solve(&self, cards: &Vec<ScratchCard>) -> Result<u32, Error> {
+        Ok(cards.iter().map(|e| e.score()).sum())
     }
 }
 
 pub struct Part2;
 
-impl Part<u32> for Part2 {
+impl Part<u32, Vec<ScratchCard>> for Part2 {
     fn expect_test(&self) -> u32 {
         30
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
-        let cards = parse(input);
-
+    fn solve(&self, cards: &Vec<ScratchCard>) -> Result<u32, Error> {
         let mut counts = vec![1; cards.len()];
 
         for card in cards {
@@ -40,7 +40,7 @@ impl Part<u32> for Part2 {
             }
         }
 
-        counts.iter().sum()
+        Ok(counts.iter().sum())
     }
 }
 
@@ -75,32 +75,35 @@ impl ScratchCard {
 }
 
 impl FromStr for ScratchCard {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split(':');
         let id =
             split.next()
-                .unwrap()
+                .context("missing card id")?
                 .split(' ')
-                .last()
-                .unwrap()
-                .parse::<usize>()
-                .unwrap();
+                .next_back()
+                .context("missing card id")?
+                .parse::<usize>()?;
 
         let mut map =
             split
                 .next()
-                .unwrap()
+                .context("missing ':' after card id")?
                 .split('|')
                 .map(|s| s.trim())
                 .map(|e|
                     e.split(' ')
                         .map(|s| s.trim())
                         .filter(|e| !e.is_empty())
-                        .map(|s| s.parse::<u32>().unwrap())
-                        .collect::<Vec<_>>())
-                .collect::<Vec<_>>();
+                        .map(|s| s.parse::<u32>())
+                        .collect::<Result<Vec<_>, _>>())
+                .collect::<Result<Vec<_>, _>>()?;
+
+        if map.len() != 2 {
+            return Err(Error::new("expected winning numbers and numbers you have separated by '|'"));
+        }
 
         Ok(ScratchCard::new(
             id - 1,
@@ -110,10 +113,6 @@ impl FromStr for ScratchCard {
     }
 }
 
-fn parse(input: &[String]) -> Vec<ScratchCard> {
-    input.iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| ScratchCard::from_str(l))
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap()
+fn parse(input: &[String]) -> Result<Vec<ScratchCard>, Error> {
+    parse_lines(input, ScratchCard::from_str)
 }
@@ -1,73 +1,72 @@
 use std::ops::Range;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration};
 
-pub fn day05() -> Day<i64, i64> {
-    Day::new(5, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(5, "If You Give A Seed A Fertilizer", || day05().f());
+
+fn day05() -> Day<i64, i64, Almanac> {
+    Day::with_parser(5, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<i64> for Part1 {
+impl Part<i64, Almanac> for Part1 {
     fn expect_test(&self) -> i64 {
         35
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        let (seeds, mappings) = parse(input);
-
-        mappings.iter()
-            .fold(seeds, |seeds, mapping| {
+    fn solve(&self, (seeds, mappings): &Almanac) -> Result<i64, Error> {
+        Ok(mappings.iter()
+            .fold(seeds.clone(), |seeds, mapping| {
                 seeds.iter()
                     .map(|&num| mapping.convert(num))
                     .collect::<Vec<_>>()
             })
             .into_iter()
             .min()
-            .unwrap()
+            .unwrap())
     }
 }
 
 pub struct Part2;
 
-impl Part<i64> for Part2 {
+impl Part<i64, Almanac> for Part2 {
     fn expect_test(&self) -> i64 {
         46
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        let (seeds, maps) = parse(input);
-
+    fn solve(&self, (seeds, maps): &Almanac) -> Result<i64, Error> {
         let flattened_ranges = FlattenedRanges::from(maps.as_slice());
         let seed_ranges = build_seed_ranges(seeds.as_slice());
 
-        seed_ranges
+        Ok(seed_ranges
             .into_iter()
             .map(|s| flattened_ranges.min(s))
             .min()
-            .unwrap()
+            .unwrap())
     }
 }
 
-fn parse(input: &[String]) -> (Vec<i64>, Vec<Mapping>) {
+type Almanac = (Vec<i64>, Vec<Mapping>);
+
+fn parse(input: &[String]) -> Result<Almanac, Error> {
     let mut elements = input.split(|l| l.is_empty()).filter(|arr| !arr.is_empty());
 
     let seeds = elements.next()
-        .unwrap()
-        .first()
-        .unwrap()
+        .and_then(|e| e.first())
+        .context("missing seeds")?
         .split(':')
-        .last()
-        .unwrap()
+        .next_back()
+        .context("missing seeds")?
         .trim()
         .split(' ')
-        .map(|s| s.parse::<i64>().unwrap())
-        .collect::<Vec<_>>();
+        .map(|s| s.parse::<i64>().map_err(|e| Error::from(e).at_line(1)))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let maps = elements.map(Mapping::from).collect::<Vec<_>>();
+    let maps = elements.map(Mapping::try_from).collect::<Result<Vec<_>, _>>()?;
 
-    (seeds, maps)
+    Ok((seeds, maps))
 }
 
 struct FlattenedRanges {
@@ -184,14 +183,16 @@ impl Mapping {
     }
 }
 
-impl From<&[String]> for Mapping {
-    fn from(value: &[String]) -> Self {
-        Mapping::new(
+impl TryFrom<&[String]> for Mapping {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
+        Ok(Mapping::new(
             value.iter()
                 .skip(1)
-                .map(|l| MappingRange::from_str(l).unwrap())
-                .collect::<Vec<_>>(),
-        )
+                .map(|l| MappingRange::from_str(l))
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
     }
 }
 
@@ -220,13 +221,17 @@ impl MappingRange {
 }
 
 impl FromStr for MappingRange {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let vec =
             s.split(' ')
-                .map(|s| s.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
+                .map(|s| s.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()?;
+
+        if vec.len() != 3 {
+            return Err(Error::new(format!("expected three numbers in mapping range '{}'", s)));
+        }
 
         Ok(MappingRange::new(vec[0], vec[1], vec[2]))
     }
@@ -1,8 +1,10 @@
 use regex::Regex;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration, Variant};
 
-pub fn day06() -> Day<u64, u64> {
+pub const REGISTRATION: Registration = Registration::new(6, "Wait For It", || day06().f());
+
+fn day06() -> Day<u64, u64> {
     Day::new(6, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -13,8 +15,12 @@ impl Part<u64> for Part1 {
         288
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        solve(input)
+    fn variants(&self) -> Vec<Variant<u64>> {
+        vec![Variant::new("brute force", |input| solve(input, solve_race_brute_force))]
+    }
+
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
+        solve(input, solve_race_algebraic)
     }
 }
 
@@ -25,21 +31,27 @@ impl Part<u64> for Part2 {
         71503
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let input =
-            input.iter()
-                .map(|s| s.replace(' ', "").replace(':', " "))
-                .collect::<Vec<_>>();
+    fn variants(&self) -> Vec<Variant<u64>> {
+        vec![Variant::new("brute force", |input| solve(&join_numbers(input), solve_race_brute_force))]
+    }
 
-        solve(&input)
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
+        solve(&join_numbers(input), solve_race_algebraic)
     }
 }
 
-fn solve(input: &[String]) -> u64 {
-    Races::from(input).races
+/// Removes the spaces between the numbers, turning the races into a single one.
+fn join_numbers(input: &[String]) -> Vec<String> {
+    input.iter()
+        .map(|s| s.replace(' ', "").replace(':', " "))
+        .collect()
+}
+
+fn solve(input: &[String], solve_race: fn(&Race) -> u64) -> Result<u64, Error> {
+    Ok(Races::try_from(input)?.races
         .iter()
-        .map(solve_race_algebraic)
-        .product()
+        .map(solve_race)
+        .product())
 }
 
 fn solve_race_algebraic(race: &Race) -> u64 {
@@ -57,7 +69,6 @@ fn solve_race_algebraic(race: &Race) -> u64 {
     max - min + 1
 }
 
-#[allow(dead_code)]
 fn solve_race_brute_force(race: &Race) -> u64 {
     (0..=race.time)
         .filter(|time_held| (race.time - time_held) * time_held > race.distance_record)
@@ -75,10 +86,12 @@ impl Races {
     }
 }
 
-impl From<&[String]> for Races {
-    fn from(value: &[String]) -> Self {
-        let times = parse_line(&value[0]);
-        let distances = parse_line(&value[1]);
+impl TryFrom<&[String]> for Races {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
+        let times = parse_line(value.first().context("missing times")?).map_err(|e| e.at_line(1))?;
+        let distances = parse_line(value.get(1).context("missing distances")?).map_err(|e| e.at_line(2))?;
 
         let races =
             times.into_iter()
@@ -86,7 +99,7 @@ impl From<&[String]> for Races {
                 .map(|(time, distance)| Race::new(time, distance))
                 .collect();
 
-        Self::new(races)
+        Ok(Self::new(races))
     }
 }
 
@@ -103,10 +116,10 @@ impl Race {
 }
 
 
-fn parse_line(s: &str) -> Vec<u64> {
-    Regex::new(r" +").unwrap()
+fn parse_line(s: &str) -> Result<Vec<u64>, Error> {
+    Ok(Regex::new(r" +").unwrap()
         .split(s)
         .skip(1)
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect::<Vec<_>>()
+        .map(|s| s.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()?)
 }
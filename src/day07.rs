@@ -1,49 +1,48 @@
 use std::collections::HashSet;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day07() -> Day<u64, u64> {
-    Day::new(7, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(7, "Camel Cards", || day07().f());
+
+fn day07() -> Day<u64, u64, Vec<Hand>> {
+    Day::with_parser(7, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Vec<Hand>> for Part1 {
     fn expect_test(&self) -> u64 {
         6440
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut hands = parse(input);
+    fn solve(&self, hands: &Vec<Hand>) -> Result<u64, Error> {
+        let mut hands = hands.clone();
 
         sort(&mut hands, calculate_card_value_part_1, HandType::calculate_part_1);
 
-        solve(&hands)
+        Ok(solve(&hands))
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, Vec<Hand>> for Part2 {
     fn expect_test(&self) -> u64 {
         5905
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut hands = parse(input);
+    fn solve(&self, hands: &Vec<Hand>) -> Result<u64, Error> {
+        let mut hands = hands.clone();
 
         sort(&mut hands, calculate_card_value_part_2, HandType::calculate_part_2);
 
-        solve(&hands)
+        Ok(solve(&hands))
     }
 }
 
-fn parse(input: &[String]) -> Vec<Hand> {
-    input.iter()
-        .filter(|s| !s.is_empty())
-        .map(|l| Hand::from_str(l).unwrap())
-        .collect::<Vec<_>>()
+fn parse(input: &[String]) -> Result<Vec<Hand>, Error> {
+    parse_lines(input, Hand::from_str)
 }
 
 fn sort(hands: &mut [Hand], calculate_card_value: fn(char) -> u32, calculate_hand_type: fn(&str) -> HandType) {
@@ -152,7 +151,7 @@ impl HandType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Hand {
     cards: String,
     bid: u32,
@@ -165,14 +164,18 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(' ');
+        let (cards, bid) = s.split_once(' ').context("expected cards and bid separated by a space")?;
+
+        if cards.chars().count() != 5 || !cards.chars().all(|c| "AKQJT98765432".contains(c)) {
+            return Err(Error::new(format!("invalid hand '{}'", cards)));
+        }
 
         Ok(Self::new(
-            split.next().unwrap().to_string(),
-            split.next().unwrap().parse().unwrap(),
+            cards.to_string(),
+            bid.parse()?,
         ))
     }
 }
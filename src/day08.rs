@@ -1,27 +1,28 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{check_cancelled, Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day08() -> Day<u64, u64> {
-    Day::new(8, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(8, "Haunted Wasteland", || day08().f());
+
+fn day08() -> Day<u64, u64, Map> {
+    Day::with_parser(8, |input| Map::try_from(input), Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Map> for Part1 {
     fn expect_test(&self) -> u64 {
         2
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let Map { instructions, nodes } = Map::from(input);
-
+    fn solve(&self, Map { instructions, nodes }: &Map) -> Result<u64, Error> {
         let mut current = "AAA";
 
         let mut step = 0;
 
         loop {
+            check_cancelled()?;
             current = nodes.get(current).unwrap().neighbour(&instructions[step % instructions.len()]);
 
             step += 1;
@@ -31,20 +32,18 @@ impl Part<u64> for Part1 {
             }
         }
 
-        step as u64
+        Ok(step as u64)
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, Map> for Part2 {
     fn expect_test(&self) -> u64 {
         6
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let Map { instructions, nodes } = Map::from(input);
-
+    fn solve(&self, Map { instructions, nodes }: &Map) -> Result<u64, Error> {
         let mut currents =
             nodes.keys()
                 .map(|name| name.as_str())
@@ -56,6 +55,8 @@ impl Part<u64> for Part2 {
         let mut step = 0;
 
         loop {
+            check_cancelled()?;
+
             currents =
                 currents.into_iter()
                     .map(|name|
@@ -76,11 +77,11 @@ impl Part<u64> for Part2 {
             }
         }
 
-        lcm(
+        Ok(lcm(
             &end_steps.iter()
                 .map(|(_, &num)| num as u64)
                 .collect::<Vec<_>>()
-        )
+        ))
     }
 }
 
@@ -105,13 +106,13 @@ enum Instruction {
 }
 
 impl TryFrom<char> for Instruction {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             'L' => Ok(Instruction::Left),
             'R' => Ok(Instruction::Right),
-            _ => Err(())
+            _ => Err(Error::new(format!("invalid instruction '{}'", value)))
         }
     }
 }
@@ -128,22 +129,27 @@ impl Map {
     }
 }
 
-impl From<&[String]> for Map {
-    fn from(value: &[String]) -> Self {
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
         let instructions =
-            value[0].chars()
-                .map(|c| Instruction::try_from(c).unwrap())
-                .collect::<Vec<_>>();
+            value.first()
+                .context("missing instructions")?
+                .chars()
+                .map(Instruction::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.at_line(1))?;
 
 
         let nodes =
-            value[2..].iter()
-                .filter(|l| !l.is_empty())
-                .map(|l| Node::from_str(l).unwrap())
+            parse_lines(value.get(2..).unwrap_or_default(), Node::from_str)
+                .map_err(|e| e.shift_line(2))?
+                .into_iter()
                 .map(|n| (n.name.clone(), n))
                 .collect::<HashMap<_, _>>();
 
-        Map::new(instructions, nodes)
+        Ok(Map::new(instructions, nodes))
     }
 }
 
@@ -168,9 +174,13 @@ impl Node {
 }
 
 impl FromStr for Node {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 16 || !s.is_ascii() {
+            return Err(Error::new(format!("invalid node '{}'", s)));
+        }
+
         Ok(Node::new(
             s[0..3].to_string(),
             s[7..10].to_string(),
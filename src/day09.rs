@@ -1,54 +1,55 @@
 use std::iter::successors;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, parse_lines, Part, Registration};
 
-pub fn day09() -> Day<i64, i64> {
-    Day::new(9, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(9, "Mirage Maintenance", || day09().f());
+
+fn day09() -> Day<i64, i64, Vec<Vec<i64>>> {
+    Day::with_parser(9, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<i64> for Part1 {
+impl Part<i64, Vec<Vec<i64>>> for Part1 {
     fn expect_test(&self) -> i64 {
         114
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        parse(input).into_iter()
+    fn solve(&self, histories: &Vec<Vec<i64>>) -> Result<i64, Error> {
+        Ok(histories.iter()
+            .cloned()
             .map(solve)
-            .sum()
+            .sum())
     }
 }
 
 pub struct Part2;
 
-impl Part<i64> for Part2 {
+impl Part<i64, Vec<Vec<i64>>> for Part2 {
     fn expect_test(&self) -> i64 {
         2
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        parse(input).into_iter()
+    fn solve(&self, histories: &Vec<Vec<i64>>) -> Result<i64, Error> {
+        Ok(histories.iter()
+            .cloned()
             .map(|mut e| {
                 e.reverse();
                 e
             })
             .map(solve)
-            .sum()
+            .sum())
     }
 }
 
-fn parse(input: &[String]) -> Vec<Vec<i64>> {
-    input.iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| parse_line(l))
-        .collect()
+fn parse(input: &[String]) -> Result<Vec<Vec<i64>>, Error> {
+    parse_lines(input, parse_line)
 }
 
-fn parse_line(line: &str) -> Vec<i64> {
-    line.split(' ')
-        .map(|e| e.parse::<i64>().unwrap())
-        .collect()
+fn parse_line(line: &str) -> Result<Vec<i64>, Error> {
+    Ok(line.split(' ')
+        .map(|e| e.parse::<i64>())
+        .collect::<Result<_, _>>()?)
 }
 
 fn solve(input: Vec<i64>) -> i64 {
@@ -1,43 +1,57 @@
 use std::collections::HashMap;
 use std::ops::{Add, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration, TestCase};
 
-pub fn day10() -> Day<i32, i32> {
-    Day::new(10, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(10, "Pipe Maze", || day10().f());
+
+fn day10() -> Day<i32, i32, Map> {
+    Day::with_parser(10, parse, Box::new(Part1 {}), Box::new(Part2 {}))
+}
+
+fn parse(input: &[String]) -> Result<Map, Error> {
+    let mut map = Map::try_from(input)?;
+    map.cull_dead_ends();
+    Ok(map)
 }
 
 pub struct Part1;
 
-impl Part<i32> for Part1 {
+impl Part<i32, Map> for Part1 {
     fn expect_test(&self) -> i32 {
         8
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let mut map = Map::from(input);
-
-        map.cull_dead_ends();
+    fn test_cases(&self) -> Vec<TestCase<i32>> {
+        vec![
+            TestCase::new("10_test1_square.txt", 4),
+        ]
+    }
 
-        (map.num_edges() / 4) as i32
+    fn solve(&self, map: &Map) -> Result<i32, Error> {
+        Ok((map.num_edges() / 4) as i32)
     }
 }
 
 pub struct Part2;
 
-impl Part<i32> for Part2 {
+impl Part<i32, Map> for Part2 {
     fn expect_test(&self) -> i32 {
         8
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        let mut map = Map::from(input);
-
-        map.cull_dead_ends();
+    fn test_cases(&self) -> Vec<TestCase<i32>> {
+        vec![
+            TestCase::new("10_test2_enclosed.txt", 4),
+            TestCase::new("10_test2_squeezed.txt", 4),
+            TestCase::new("10_test2_junk.txt", 10),
+        ]
+    }
 
+    fn solve(&self, map: &Map) -> Result<i32, Error> {
         let polygon = map.calculate_polygon();
 
-        calculate_area(&polygon) - calculate_trimmings(&polygon)
+        Ok(calculate_area(&polygon) - calculate_trimmings(&polygon))
     }
 }
 
@@ -104,8 +118,10 @@ impl Map {
     }
 }
 
-impl From<&[String]> for Map {
-    fn from(value: &[String]) -> Self {
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
         let mut connections_map = HashMap::new();
         let mut start = None;
 
@@ -117,26 +133,26 @@ impl From<&[String]> for Map {
                     start = Some(p);
                 }
 
-                let neighbours = neighbours(c).into_iter().map(|e| e + p).collect::<Vec<_>>();
+                let neighbours = neighbours(c).map_err(|e| e.at_line(y + 1))?.into_iter().map(|e| e + p).collect::<Vec<_>>();
                 connections_map.insert(p, neighbours);
             }
         }
 
-        Map::new(start.unwrap(), connections_map)
+        Ok(Map::new(start.context("missing start tile 'S'")?, connections_map))
     }
 }
 
-fn neighbours(c: char) -> Vec<Vec2> {
+fn neighbours(c: char) -> Result<Vec<Vec2>, Error> {
     match c {
-        '|' => vec![Vec2::NORTH, Vec2::SOUTH],
-        '-' => vec![Vec2::EAST, Vec2::WEST],
-        'L' => vec![Vec2::NORTH, Vec2::EAST],
-        'J' => vec![Vec2::NORTH, Vec2::WEST],
-        '7' => vec![Vec2::SOUTH, Vec2::WEST],
-        'F' => vec![Vec2::EAST, Vec2::SOUTH],
-        '.' => vec![],
-        'S' => vec![Vec2::NORTH, Vec2::EAST, Vec2::SOUTH, Vec2::WEST],
-        _ => panic!(),
+        '|' => Ok(vec![Vec2::NORTH, Vec2::SOUTH]),
+        '-' => Ok(vec![Vec2::EAST, Vec2::WEST]),
+        'L' => Ok(vec![Vec2::NORTH, Vec2::EAST]),
+        'J' => Ok(vec![Vec2::NORTH, Vec2::WEST]),
+        '7' => Ok(vec![Vec2::SOUTH, Vec2::WEST]),
+        'F' => Ok(vec![Vec2::EAST, Vec2::SOUTH]),
+        '.' => Ok(vec![]),
+        'S' => Ok(vec![Vec2::NORTH, Vec2::EAST, Vec2::SOUTH, Vec2::WEST]),
+        _ => Err(Error::new(format!("invalid tile '{}'", c))),
     }
 }
 
@@ -1,8 +1,10 @@
 use std::ops::{Add, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, param, Params, Part, Registration, TestCase};
 
-pub fn day11() -> Day<i64, i64> {
+pub const REGISTRATION: Registration = Registration::new(11, "Cosmic Expansion", || day11().f());
+
+fn day11() -> Day<i64, i64> {
     Day::new(11, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -13,8 +15,13 @@ impl Part<i64> for Part1 {
         374
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        solve(parse(input, 2))
+    /// How many rows or columns each empty row or column becomes.
+    fn params(&self) -> Params {
+        Params::default().with("expansion", 2)
+    }
+
+    fn solve(&self, input: &[String]) -> Result<i64, Error> {
+        Ok(solve(parse(input, param("expansion")?)?))
     }
 }
 
@@ -22,11 +29,23 @@ pub struct Part2;
 
 impl Part<i64> for Part2 {
     fn expect_test(&self) -> i64 {
-        82000210
+        8410
+    }
+
+    fn params(&self) -> Params {
+        Params::default().with("expansion", 1_000_000)
+    }
+
+    fn test_params(&self) -> Params {
+        Params::default().with("expansion", 100)
     }
 
-    fn solve(&self, input: &[String]) -> i64 {
-        solve(parse(input, 1_000_000))
+    fn test_cases(&self) -> Vec<TestCase<i64>> {
+        vec![TestCase::new("11_test.txt", 1030).with_param("expansion", 10)]
+    }
+
+    fn solve(&self, input: &[String]) -> Result<i64, Error> {
+        Ok(solve(parse(input, param("expansion")?)?))
     }
 }
 
@@ -41,9 +60,17 @@ fn solve(galaxies: Vec<Vec2>) -> i64 {
         .sum::<i64>()
 }
 
-fn parse(input: &[String], scale: i64) -> Vec<Vec2> {
+fn parse(input: &[String], scale: i64) -> Result<Vec<Vec2>, Error> {
     let input = input.iter().filter(|l| !l.is_empty()).collect::<Vec<_>>();
 
+    if input.is_empty() {
+        return Err(Error::new("input is empty"));
+    }
+
+    if let Some(y) = input.iter().position(|l| l.len() != input[0].len() || l.chars().any(|c| c != '.' && c != '#')) {
+        return Err(Error::new("lines must be of equal length and only contain '.' and '#'").at_line(y + 1));
+    }
+
     let width = input[0].len();
     let height = input.len();
 
@@ -59,7 +86,7 @@ fn parse(input: &[String], scale: i64) -> Vec<Vec2> {
             .map(|x| x as i64)
             .collect::<Vec<_>>();
 
-    input.iter()
+    Ok(input.iter()
         .enumerate()
         .flat_map(|(y, l)|
             l.chars().enumerate()
@@ -72,7 +99,7 @@ fn parse(input: &[String], scale: i64) -> Vec<Vec2> {
                 v_expand.iter().filter(|&&y| y < galaxy.y).count() as i64 * (scale - 1),
             )
         )
-        .collect()
+        .collect())
 }
 
 const fn v(x: i64, y: i64) -> Vec2 {
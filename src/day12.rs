@@ -1,42 +1,44 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day12() -> Day<u64, u64> {
-    Day::new(12, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(12, "Hot Springs", || day12().f());
+
+fn day12() -> Day<u64, u64, Vec<Record>> {
+    Day::with_parser(12, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Vec<Record>> for Part1 {
     fn expect_test(&self) -> u64 {
         21
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        parse(input).into_iter()
+    fn solve(&self, records: &Vec<Record>) -> Result<u64, Error> {
+        Ok(records.iter()
             .map(solve)
-            .sum()
+            .sum())
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, Vec<Record>> for Part2 {
     fn expect_test(&self) -> u64 {
         525152
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        parse(input).into_iter()
+    fn solve(&self, records: &Vec<Record>) -> Result<u64, Error> {
+        Ok(records.iter()
             .map(|r| r.extend(5))
-            .map(solve)
-            .sum()
+            .map(|r| solve(&r))
+            .sum())
     }
 }
 
-fn solve(r: Record) -> u64 {
+fn solve(r: &Record) -> u64 {
     solve_rec(r.chars.as_slice(), r.working_groups.as_slice(), &mut HashMap::new(), 0, 0, 0)
 }
 
@@ -87,11 +89,8 @@ fn solve_rec(chars: &[char], groups: &[usize], cache: &mut HashMap<(usize, usize
     total
 }
 
-fn parse(input: &[String]) -> Vec<Record> {
-    input.iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| Record::from_str(l).unwrap())
-        .collect()
+fn parse(input: &[String]) -> Result<Vec<Record>, Error> {
+    parse_lines(input, Record::from_str)
 }
 
 #[derive(Debug)]
@@ -117,19 +116,21 @@ impl Record {
 }
 
 impl FromStr for Record {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(' ');
+        let (chars, working_groups) = s.split_once(' ').context("expected springs and groups separated by a space")?;
 
-        let chars =
-            split.next().unwrap().chars()
-                .collect::<Vec<_>>();
+        let chars = chars.chars().collect::<Vec<_>>();
+
+        if chars.iter().any(|c| !".#?".contains(*c)) {
+            return Err(Error::new(format!("invalid springs '{}'", s)));
+        }
 
         let working_groups =
-            split.next().unwrap().split(',')
-                .map(|e| e.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
+            working_groups.split(',')
+                .map(|e| e.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Record::new(
             chars,
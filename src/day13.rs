@@ -1,6 +1,8 @@
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration};
 
-pub fn day13() -> Day<u64, u64> {
+pub const REGISTRATION: Registration = Registration::new(13, "Point of Incidence", || day13().f());
+
+fn day13() -> Day<u64, u64> {
     Day::new(13, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -11,7 +13,7 @@ impl Part<u64> for Part1 {
         405
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
         solve(input, 0)
     }
 }
@@ -24,19 +26,19 @@ impl Part<u64> for Part2 {
         400
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
         solve(input, 1)
     }
 }
 
-fn solve(input: &[String], smudge_count: usize) -> u64 {
+fn solve(input: &[String], smudge_count: usize) -> Result<u64, Error> {
     parse(input).iter()
         .map(|map|
             map.find_vertical_mirror(smudge_count)
                 .map(|e| e * 100)
-                .unwrap_or_else(||
-                    map.rotate().find_vertical_mirror(smudge_count).unwrap()
-                ) as u64
+                .or_else(|| map.rotate().find_vertical_mirror(smudge_count))
+                .context("pattern has no line of reflection")
+                .map(|e| e as u64)
         )
         .sum()
 }
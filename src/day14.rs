@@ -2,40 +2,43 @@ use std::collections::HashMap;
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, parse_lines, Part, Registration};
 
-pub fn day14() -> Day<u64, u64> {
-    Day::new(14, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(14, "Parabolic Reflector Dish", || day14().f());
+
+fn day14() -> Day<u64, u64, Map> {
+    Day::with_parser(14, |input| Map::try_from(input), Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Map> for Part1 {
     fn expect_test(&self) -> u64 {
         136
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut map = Map::from(input);
+    fn solve(&self, map: &Map) -> Result<u64, Error> {
+        let mut map = map.clone();
         map.make_step(Vec2::NORTH);
-        map.count_load()
+        Ok(map.count_load())
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, Map> for Part2 {
     fn expect_test(&self) -> u64 {
         64
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut map = Map::from(input);
+    fn solve(&self, map: &Map) -> Result<u64, Error> {
+        let mut map = map.clone();
         map.make_cycles(1_000_000_000);
-        map.count_load()
+        Ok(map.count_load())
     }
 }
 
+#[derive(Clone)]
 struct Map {
     raw: Vec<Vec<char>>,
     width: usize,
@@ -146,16 +149,26 @@ impl Map {
 
 const ROCK: char = 'O';
 const EMPTY: char = '.';
+const CUBE: char = '#';
+
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
 
-impl From<&[String]> for Map {
-    fn from(value: &[String]) -> Self {
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
         let raw =
-            value.iter()
-                .filter(|l| !l.is_empty())
-                .map(|line| line.chars().collect())
-                .collect();
+            parse_lines(value, |line| {
+                if line.chars().all(|c| c == ROCK || c == EMPTY || c == CUBE) {
+                    Ok(line.chars().collect())
+                } else {
+                    Err(Error::new(format!("invalid row '{}'", line)))
+                }
+            })?;
+
+        if raw.is_empty() {
+            return Err(Error::new("input is empty"));
+        }
 
-        Map::new(raw)
+        Ok(Map::new(raw))
     }
 }
 
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration};
 
-pub fn day15() -> Day<i32, i32> {
+pub const REGISTRATION: Registration = Registration::new(15, "Lens Library", || day15().f());
+
+fn day15() -> Day<i32, i32> {
     Day::new(15, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -13,8 +15,8 @@ impl Part<i32> for Part1 {
         1320
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
-        parse(input).iter().map(|&e| hash(e)).sum()
+    fn solve(&self, input: &[String]) -> Result<i32, Error> {
+        Ok(parse(input)?.iter().map(|&e| hash(e)).sum())
     }
 }
 
@@ -25,31 +27,27 @@ impl Part<i32> for Part2 {
         145
     }
 
-    fn solve(&self, input: &[String]) -> i32 {
+    fn solve(&self, input: &[String]) -> Result<i32, Error> {
         let mut map = HashMap::<i32, Vec<(&str, i32)>>::new();
 
-        for element in input.first().unwrap().split(',') {
-            if element.contains('-') {
-                let name = element.split('-').next().unwrap();
+        for element in parse(input)? {
+            if let Some(name) = element.strip_suffix('-') {
                 let hash_number = hash(name);
 
                 let bucket = map.entry(hash_number).or_default();
 
                 bucket.retain(|e| e.0 != name);
             } else {
-                let mut split = element.split('=');
-
-                let name = split.next().unwrap();
+                let (name, number) = element.split_once('=').context(&format!("invalid step '{}'", element))?;
                 let hash_number = hash(name);
-                let number = split.next().unwrap();
 
                 let bucket = map.entry(hash_number).or_default();
 
-                let new_element = (name, number.parse::<i32>().unwrap());
+                let new_element = (name, number.parse::<i32>().map_err(|e| Error::from(e).at_line(1))?);
                 let mut inserted = false;
-                for i in 0..bucket.len() {
-                    if bucket[i].0 == name {
-                        bucket[i] = new_element;
+                for element in bucket.iter_mut() {
+                    if element.0 == name {
+                        *element = new_element;
                         inserted = true;
                         break;
                     }
@@ -60,19 +58,19 @@ impl Part<i32> for Part2 {
             }
         }
 
-        map.iter()
+        Ok(map.iter()
             .map(|(bucket_index, bucket)|
                 (*bucket_index + 1) * bucket.iter()
                     .enumerate()
                     .map(|(element_index, element)| (element_index as i32 + 1) * element.1)
                     .sum::<i32>()
             )
-            .sum()
+            .sum())
     }
 }
 
-fn parse(input: &[String]) -> Vec<&str> {
-    input.first().unwrap().split(',').collect()
+fn parse(input: &[String]) -> Result<Vec<&str>, Error> {
+    Ok(input.first().context("input is empty")?.split(',').collect())
 }
 
 fn hash(str: &str) -> i32 {
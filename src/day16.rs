@@ -2,35 +2,34 @@ use std::collections::HashSet;
 use std::hash::Hash;
 use std::ops::{Add, Neg};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, parse_lines, Part, Registration};
 
-pub fn day16() -> Day<usize, usize> {
-    Day::new(16, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(16, "The Floor Will Be Lava", || day16().f());
+
+fn day16() -> Day<usize, usize, Map> {
+    Day::with_parser(16, |input| Map::try_from(input), Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<usize> for Part1 {
+impl Part<usize, Map> for Part1 {
     fn expect_test(&self) -> usize {
         46
     }
 
-    fn solve(&self, input: &[String]) -> usize {
-        Map::from(input)
-            .calculate_energy2(Ray::new(v(0, 0), Vec2::EAST))
+    fn solve(&self, map: &Map) -> Result<usize, Error> {
+        Ok(map.calculate_energy2(Ray::new(v(0, 0), Vec2::EAST)))
     }
 }
 
 pub struct Part2;
 
-impl Part<usize> for Part2 {
+impl Part<usize, Map> for Part2 {
     fn expect_test(&self) -> usize {
         51
     }
 
-    fn solve(&self, input: &[String]) -> usize {
-        let map = Map::from(input);
-
+    fn solve(&self, map: &Map) -> Result<usize, Error> {
         let i1 =
             (0..(map.width() as i32))
                 .flat_map(|x| vec![Ray::new(v(x, 0), Vec2::SOUTH), Ray::new(v(x, map.height() as i32 - 1), Vec2::NORTH)]);
@@ -39,10 +38,10 @@ impl Part<usize> for Part2 {
             (0..(map.height() as i32))
                 .flat_map(|y| vec![Ray::new(v(0, y), Vec2::EAST), Ray::new(v(map.width() as i32 - 1, y), Vec2::WEST)]);
 
-        i1.chain(i2)
+        Ok(i1.chain(i2)
             .map(|r| map.calculate_energy2(r))
             .max()
-            .unwrap()
+            .unwrap())
     }
 }
 
@@ -151,14 +150,23 @@ impl Map {
     }
 }
 
-impl From<&[String]> for Map {
-    fn from(value: &[String]) -> Self {
-        let raw = value.iter()
-            .filter(|l| !l.is_empty())
-            .map(|l| l.chars().collect())
-            .collect();
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
+        let raw = parse_lines(value, |l| {
+            if l.chars().all(|c| ".|-/\\".contains(c)) {
+                Ok(l.chars().collect())
+            } else {
+                Err(Error::new(format!("invalid row '{}'", l)))
+            }
+        })?;
+
+        if raw.is_empty() {
+            return Err(Error::new("input is empty"));
+        }
 
-        Self::new(raw)
+        Ok(Self::new(raw))
     }
 }
 
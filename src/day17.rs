@@ -3,9 +3,11 @@ use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::ops::{Add, Mul, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day17() -> Day<u16, u16> {
+pub const REGISTRATION: Registration = Registration::new(17, "Clumsy Crucible", || day17().f());
+
+fn day17() -> Day<u16, u16> {
     Day::new(17, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -16,7 +18,7 @@ impl Part<u16> for Part1 {
         102
     }
 
-    fn solve(&self, input: &[String]) -> u16 {
+    fn solve(&self, input: &[String]) -> Result<u16, Error> {
         solvify(input, 1, 3)
     }
 }
@@ -28,18 +30,18 @@ impl Part<u16> for Part2 {
         94
     }
 
-    fn solve(&self, input: &[String]) -> u16 {
+    fn solve(&self, input: &[String]) -> Result<u16, Error> {
         solvify(input, 4, 10)
     }
 }
 
-fn solvify(input: &[String], min_step: usize, max_step: usize) -> u16 {
-    let map = Map::from(input, min_step, max_step);
+fn solvify(input: &[String], min_step: usize, max_step: usize) -> Result<u16, Error> {
+    let map = Map::from(input, min_step, max_step)?;
 
     let start = v(0, 0);
     let goal = v_usize(map.width - 1, map.height - 1);
 
-    map.find_path(start, goal) as u16
+    Ok(map.find_path(start, goal) as u16)
 }
 
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
@@ -99,14 +101,15 @@ impl Map {
         Self { edges, width, height }
     }
 
-    fn from(value: &[String], min_step: usize, max_step: usize) -> Self {
+    fn from(value: &[String], min_step: usize, max_step: usize) -> Result<Self, Error> {
         let raw =
-            value.iter()
-                .filter(|l| !l.is_empty())
-                .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect::<Vec<_>>())
-                .collect::<Vec<_>>();
+            parse_lines(value, |l|
+                l.chars()
+                    .map(|c| c.to_digit(10).context(&format!("invalid heat loss '{}'", c)))
+                    .collect::<Result<Vec<_>, _>>()
+            )?;
 
-        let width = raw[0].len();
+        let width = raw.first().context("input is empty")?.len();
         let height = raw.len();
 
         let mut edge_map = HashMap::new();
@@ -140,7 +143,7 @@ impl Map {
             }
         }
 
-        Map::new(edge_map, width, height)
+        Ok(Map::new(edge_map, width, height))
     }
 
     fn find_path(&self, start: Vec2, goal: Vec2) -> i16 {
@@ -207,7 +210,7 @@ impl PartialEq<Self> for HeapEdge {
 
 impl PartialOrd<Self> for HeapEdge {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        other.1.partial_cmp(&self.1)
+        Some(self.cmp(other))
     }
 }
 
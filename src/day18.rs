@@ -1,33 +1,35 @@
 use std::ops::{Add, AddAssign, Mul, Sub};
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, parse_lines, Part, Registration};
 
-pub fn day18() -> Day<u64, u64> {
-    Day::new(18, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(18, "Lavaduct Lagoon", || day18().f());
+
+fn day18() -> Day<u64, u64, Instructions> {
+    Day::with_parser(18, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 pub struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Instructions> for Part1 {
     fn expect_test(&self) -> u64 {
         62
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        solve(&parse(input).0)
+    fn solve(&self, (instructions, _): &Instructions) -> Result<u64, Error> {
+        Ok(solve(instructions))
     }
 }
 
 pub struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, Instructions> for Part2 {
     fn expect_test(&self) -> u64 {
         952408144115
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        solve(&parse(input).1)
+    fn solve(&self, (_, instructions): &Instructions) -> Result<u64, Error> {
+        Ok(solve(instructions))
     }
 }
 
@@ -56,34 +58,39 @@ fn calculate_area(polygon: &[Vec2]) -> u64 {
     }).sum::<i64>().unsigned_abs() / 2 + 1
 }
 
-fn parse(input: &[String]) -> (Vec<Vec2>, Vec<Vec2>) {
-    input.iter()
-        .filter(|l| !l.is_empty())
-        .map(|l| parse_line(l))
-        .unzip()
+/// The dig plan read as direction and distance, and read from the color.
+type Instructions = (Vec<Vec2>, Vec<Vec2>);
+
+fn parse(input: &[String]) -> Result<Instructions, Error> {
+    Ok(parse_lines(input, parse_line)?.into_iter().unzip())
 }
 
-fn parse_line(line: &str) -> (Vec2, Vec2) {
+fn parse_line(line: &str) -> Result<(Vec2, Vec2), Error> {
     let mut split = line.split(' ');
 
-    let direction = Vec2::from_str(split.next().unwrap()).unwrap();
-    let distance = split.next().unwrap().parse::<i64>().unwrap();
+    let direction = Vec2::from_str(split.next().context("missing direction")?)?;
+    let distance = split.next().context("missing distance")?.parse::<i64>()?;
 
     let color =
-        split.next().unwrap()[2..8].chars()
-            .map(|e| e.to_digit(16).unwrap())
+        split.next()
+            .and_then(|e| e.get(2..8))
+            .context("missing color")?
+            .chars()
+            .map(|e| e.to_digit(16).context(&format!("invalid hex digit '{}'", e)))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .rev()
             .enumerate()
             .map(|(idx, e)| 16_usize.pow(idx as u32) * e as usize)
             .sum::<usize>();
 
     let distance2 = (color >> 4) as i64;
-    let direction2 = Vec2::DIRECTIONS[color % 16];
+    let direction2 = *Vec2::DIRECTIONS.get(color % 16).context("invalid direction in color")?;
 
-    (
+    Ok((
         direction * distance,
         direction2 * distance2,
-    )
+    ))
 }
 
 const fn v(x: i64, y: i64) -> Vec2 {
@@ -114,7 +121,7 @@ impl Vec2 {
 }
 
 impl FromStr for Vec2 {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -122,7 +129,7 @@ impl FromStr for Vec2 {
             "R" => Ok(Vec2::EAST),
             "D" => Ok(Vec2::SOUTH),
             "L" => Ok(Vec2::WEST),
-            _ => Err(())
+            _ => Err(Error::new(format!("invalid direction '{}'", s)))
         }
     }
 }
@@ -2,48 +2,49 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use std::str::FromStr;
 
-use crate::harness::{Day, Part};
+use crate::harness::{Context, Day, Error, Part, Registration};
 
-pub fn day19() -> Day<u32, u64> {
-    Day::new(19, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(19, "Aplenty", || day19().f());
+
+fn day19() -> Day<u32, u64, System> {
+    Day::with_parser(19, parse, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 struct Part1;
 
-impl Part<u32> for Part1 {
+impl Part<u32, System> for Part1 {
     fn expect_test(&self) -> u32 {
         19114
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
-        let (workflows, mut machine_parts) = parse(input);
-
-        machine_parts.retain(|machine_part| {
-            let mut next = &Next::Workflow("in".to_string());
-
-            while let Next::Workflow(name) = next {
-                next = workflows[name].next(machine_part);
-            }
+    fn solve(&self, (workflows, machine_parts): &System) -> Result<u32, Error> {
+        Ok(machine_parts.iter()
+            .filter(|machine_part| {
+                let mut next = &Next::Workflow("in".to_string());
 
-            matches!(next, Next::Accept)
-        });
+                while let Next::Workflow(name) = next {
+                    next = workflows[name].next(machine_part);
+                }
 
-        machine_parts.iter().map(|e| e.x + e.m + e.a + e.s).sum()
+                matches!(next, Next::Accept)
+            })
+            .map(|e| e.x + e.m + e.a + e.s)
+            .sum())
     }
 }
 
 struct Part2;
 
-impl Part<u64> for Part2 {
+impl Part<u64, System> for Part2 {
     fn expect_test(&self) -> u64 {
         167409079868000
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        solve_part_2(&parse(input).0)
+    fn solve(&self, (workflows, _): &System) -> Result<u64, Error> {
+        Ok(solve_part_2(workflows)
             .iter()
             .map(RecRanges::count_combinations)
-            .sum()
+            .sum())
     }
 }
 
@@ -148,21 +149,27 @@ impl RecRanges {
     }
 }
 
-fn parse(input: &[String]) -> (HashMap<String, Workflow>, Vec<MachinePart>) {
+type System = (HashMap<String, Workflow>, Vec<MachinePart>);
+
+fn parse(input: &[String]) -> Result<System, Error> {
     let mut split = input.split(|e| e.is_empty());
 
+    let workflow_lines = split.next().context("missing workflows")?;
+
     let workflows =
-        split.next().unwrap().iter()
-            .map(|e| Workflow::from_str(e).unwrap())
-            .map(|e| (e.name.clone(), e))
-            .collect::<HashMap<_, _>>();
+        workflow_lines.iter()
+            .enumerate()
+            .map(|(idx, e)| Workflow::from_str(e).map_err(|e| e.at_line(idx + 1)))
+            .map(|e| e.map(|e| (e.name.clone(), e)))
+            .collect::<Result<HashMap<_, _>, _>>()?;
 
     let machine_parts =
-        split.next().unwrap().iter()
-            .map(|e| MachinePart::from_str(e).unwrap())
-            .collect::<Vec<_>>();
+        split.next().context("missing machine parts")?.iter()
+            .enumerate()
+            .map(|(idx, e)| MachinePart::from_str(e).map_err(|e| e.at_line(workflow_lines.len() + idx + 2)))
+            .collect::<Result<Vec<_>, _>>()?;
 
-    (workflows, machine_parts)
+    Ok((workflows, machine_parts))
 }
 
 #[derive(Debug)]
@@ -173,7 +180,7 @@ enum Next {
 }
 
 impl FromStr for Next {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -190,12 +197,14 @@ enum Operator {
     LessThan,
 }
 
-impl From<char> for Operator {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for Operator {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '<' => Self::LessThan,
-            '>' => Self::GreaterThan,
-            _ => panic!(),
+            '<' => Ok(Self::LessThan),
+            '>' => Ok(Self::GreaterThan),
+            _ => Err(Error::new(format!("invalid operator '{}'", value))),
         }
     }
 }
@@ -224,15 +233,16 @@ impl Workflow {
 }
 
 impl FromStr for Workflow {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut split = s.split('{');
-        let name = split.next().unwrap();
+        let name = split.next().context("missing workflow name")?;
 
-        let remainder = split.next().unwrap();
         let remainder =
-            remainder[0..(remainder.len() - 1)]
+            split.next()
+                .and_then(|e| e.strip_suffix('}'))
+                .context(&format!("invalid workflow '{}'", s))?
                 .split(',')
                 .collect::<Vec<_>>();
 
@@ -241,13 +251,13 @@ impl FromStr for Workflow {
                 .rev()
                 .skip(1)
                 .rev()
-                .map(|e| Rule::from_str(e).unwrap())
-                .collect::<Vec<_>>();
+                .map(|e| Rule::from_str(e))
+                .collect::<Result<Vec<_>, _>>()?;
 
         let accept =
             remainder
-                .last().unwrap()
-                .parse().unwrap();
+                .last().context("missing fallback rule")?
+                .parse()?;
 
         Ok(Self::new(name.to_string(), rules, accept))
     }
@@ -283,16 +293,16 @@ impl Rule {
 }
 
 impl FromStr for Rule {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut chars = s.chars();
-        let variable = chars.next().unwrap();
-        let condition = chars.next().unwrap().into();
+        let variable = chars.next().filter(|c| "xmas".contains(*c)).context(&format!("invalid rule '{}'", s))?;
+        let condition = chars.next().context(&format!("invalid rule '{}'", s))?.try_into()?;
 
         let mut split = s[2..].split(':');
-        let value = split.next().unwrap().parse().unwrap();
-        let next_workflow = split.next().unwrap().parse().unwrap();
+        let value = split.next().context("missing rule value")?.parse()?;
+        let next_workflow = split.next().context("missing rule target")?.parse()?;
 
         Ok(Self::new(variable, condition, value, next_workflow))
     }
@@ -313,15 +323,20 @@ impl MachinePart {
 }
 
 impl FromStr for MachinePart {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let vec =
-            s[1..(s.len() - 1)]
+            s.strip_prefix('{')
+                .and_then(|e| e.strip_suffix('}'))
+                .context(&format!("invalid machine part '{}'", s))?
                 .split(',')
-                .map(|e| &e[2..])
-                .map(|e| e.parse().unwrap())
-                .collect::<Vec<_>>();
+                .map(|e| e.get(2..).context("missing rating")?.parse().map_err(Error::from))
+                .collect::<Result<Vec<_>, _>>()?;
+
+        if vec.len() != 4 {
+            return Err(Error::new(format!("expected four ratings in machine part '{}'", s)));
+        }
 
         Ok(Self::new(vec[0], vec[1], vec[2], vec[3]))
     }
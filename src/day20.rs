@@ -1,9 +1,11 @@
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Debug;
 
-use crate::harness::{Day, Part};
+use crate::harness::{check_cancelled, Context, Day, Error, parse_lines, Part, Registration, TestCase};
 
-pub fn day20() -> Day<u64, u64> {
+pub const REGISTRATION: Registration = Registration::new(20, "Pulse Propagation", || day20().f());
+
+fn day20() -> Day<u64, u64> {
     Day::new(20, Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
@@ -14,15 +16,21 @@ impl Part<u64> for Part1 {
         32000000
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut modules = parse(input);
+    fn test_cases(&self) -> Vec<TestCase<u64>> {
+        vec![
+            TestCase::new("20_test1_output.txt", 11687500),
+        ]
+    }
+
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
+        let mut modules = parse(input)?;
 
         let mut low_count = 0;
         let mut high_count = 0;
 
         let n = 1000;
 
-        for i in 0..n {
+        for _ in 0..n {
             let mut open_list = VecDeque::new();
 
             open_list.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
@@ -43,7 +51,7 @@ impl Part<u64> for Part1 {
             }
         }
 
-        low_count * high_count
+        Ok(low_count * high_count)
     }
 }
 
@@ -54,37 +62,38 @@ impl Part<u64> for Part2 {
         0
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let mut modules = parse(input);
+    fn solve(&self, input: &[String]) -> Result<u64, Error> {
+        let mut modules = parse(input)?;
 
         // Skip test if tx module doesn't exist
         if !modules.values().any(|e| e.connections.contains(&"tx".to_string())) {
-            return self.expect_test();
+            return Ok(self.expect_test());
         }
 
-        let map = Self::find_first_low_pulses_for_ls_modules(&mut modules);
+        let map = Self::find_first_low_pulses_for_ls_modules(&mut modules)?;
         
-        lcm(&map.values().copied().collect::<Vec<_>>())
+        Ok(lcm(&map.values().copied().collect::<Vec<_>>()))
     }
 }
 
 impl Part2 {
-    fn find_first_low_pulses_for_ls_modules(modules: &mut HashMap<String, Module>) -> HashMap<String, u64> {
+    fn find_first_low_pulses_for_ls_modules(modules: &mut HashMap<String, Module>) -> Result<HashMap<String, u64>, Error> {
         let mut first: HashMap<String, u64> = HashMap::new();
 
         let mut n = 0;
 
         loop {
+            check_cancelled()?;
             n += 1;
             let mut open_list = VecDeque::new();
 
             open_list.push_back(("button".to_string(), "broadcaster".to_string(), Pulse::Low));
 
             while let Some((from, to, pulse)) = open_list.pop_front() {
-                if (to == "ls" && matches!(pulse, Pulse::High)) {
+                if to == "ls" && matches!(pulse, Pulse::High) {
                     first.entry(from.clone()).or_insert(n);
-                    if (first.len() == 4) {
-                        return first;
+                    if first.len() == 4 {
+                        return Ok(first);
                     }
                 }
 
@@ -114,14 +123,11 @@ fn lcm(nums: &[u64]) -> u64 {
         .unwrap()
 }
 
-fn parse(input: &[String]) -> HashMap<String, Module> {
+fn parse(input: &[String]) -> Result<HashMap<String, Module>, Error> {
     let map =
-        input.iter()
-            .filter(|e| !e.is_empty())
-            .map(|e| {
-                let line = parse_line(e);
-                (line.1, (line.0, line.2))
-            })
+        parse_lines(input, parse_line)?
+            .into_iter()
+            .map(|line| (line.1, (line.0, line.2)))
             .collect::<HashMap<_, _>>();
 
     let map1 =
@@ -130,7 +136,7 @@ fn parse(input: &[String]) -> HashMap<String, Module> {
             .collect::<HashMap<_, _>>();
 
 
-    map.into_iter()
+    Ok(map.into_iter()
         .map(|(key, (prefix, succesors))| {
             let option = map1.get(key).unwrap().iter().map(|e| e.to_string()).collect();
             let receiver: Box<dyn Receiver> = match prefix {
@@ -142,12 +148,12 @@ fn parse(input: &[String]) -> HashMap<String, Module> {
             Module::new(key.to_string(), succesors.iter().map(|e| e.to_string()).collect(), receiver)
         })
         .map(|e| (e.name.clone(), e))
-        .collect::<HashMap<_, _>>()
+        .collect::<HashMap<_, _>>())
 }
 
-fn parse_line(line: &str) -> (Option<char>, &str, Vec<&str>) {
+fn parse_line(line: &str) -> Result<(Option<char>, &str, Vec<&str>), Error> {
     let mut split = line.split("->").map(|e| e.trim());
-    let name = split.next().unwrap();
+    let name = split.next().context("missing module name")?;
 
     let x =
         if let Some(end) = name.strip_prefix('%') {
@@ -158,9 +164,9 @@ fn parse_line(line: &str) -> (Option<char>, &str, Vec<&str>) {
             (None, name)
         };
 
-    let successors = split.next().unwrap().split(',').map(|e| e.trim()).collect();
+    let successors = split.next().context("missing '->' after module name")?.split(',').map(|e| e.trim()).collect();
 
-    (x.0, x.1, successors)
+    Ok((x.0, x.1, successors))
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -194,6 +200,7 @@ impl Module {
 trait Receiver: Debug {
     fn receive_pulse(&mut self, from: &str, pulse: Pulse) -> Option<Pulse>;
 
+    #[allow(dead_code)]
     fn reset(&mut self);
 }
 
@@ -1,108 +1,154 @@
 use std::collections::HashSet;
+use std::mem;
 use std::ops::{Add, AddAssign, Mul, Sub};
 
-use crate::harness::{Day, Part};
+use crate::harness::{check_cancelled, Context, Day, Error, param, Params, Part, Registration, TestCase};
 
-pub fn day21() -> Day<u64, u64> {
-    Day::new(21, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(21, "Step Counter", || day21().f());
+
+fn day21() -> Day<u64, u64, Map> {
+    Day::with_parser(21, |input| Map::try_from(input), Box::new(Part1 {}), Box::new(Part2 {}))
 }
 
 struct Part1;
 
-impl Part<u64> for Part1 {
+impl Part<u64, Map> for Part1 {
     fn expect_test(&self) -> u64 {
         16
     }
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let map = Map::from(input);
-
-        let steps: usize = if map.width() < 12 {
-            6
-        } else {
-            64
-        };
+    /// How many steps the elf takes.
+    fn params(&self) -> Params {
+        Params::default().with("steps", 64)
+    }
 
-        let vec = solve_rec(&map, &mut HashSet::new(), map.starting_position, steps);
+    fn test_params(&self) -> Params {
+        Params::default().with("steps", 6)
+    }
 
-        vec.into_iter().collect::<HashSet<_>>().len() as u64
+    fn solve(&self, map: &Map) -> Result<u64, Error> {
+        Ok(Walk::new(map, false).reachable(param("steps")?))
     }
 }
 
-fn solve_rec(map: &Map, cache: &mut HashSet<(Vec2, usize)>, current_position: Vec2, steps_remaining: usize) -> Vec<Vec2> {
-    if steps_remaining == 0 {
-        return vec![current_position];
-    }
+/// Walks from the start one step at a time, counting the plots first reached after each number of steps.
+/// A plot reached after `d` steps can be reached again after `d + 2`, `d + 4`, ... steps by stepping back and forth.
+struct Walk<'a> {
+    map: &'a Map,
+    /// Whether the map repeats infinitely in every direction.
+    infinite: bool,
+    previous: HashSet<Vec2>,
+    current: HashSet<Vec2>,
+    /// Number of plots first reached after each number of steps.
+    reached: Vec<u64>,
+}
 
-    let key = (current_position, steps_remaining);
+impl<'a> Walk<'a> {
+    fn new(map: &'a Map, infinite: bool) -> Self {
+        Self { map, infinite, previous: HashSet::new(), current: HashSet::from([map.starting_position]), reached: vec![1] }
+    }
 
-    if cache.contains(&key) {
-        return vec![];
+    fn is_plot(&self, pos: &Vec2) -> bool {
+        if self.infinite {
+            matches!(self.map.get_infinite(pos), Tile::Plot)
+        } else {
+            matches!(self.map.get(pos), Some(Tile::Plot))
+        }
     }
 
-    let mut result = vec![];
+    /// Takes one more step. Every neighbour of a plot is one step closer or further, as the grid is bipartite,
+    /// so only the plots of the previous step need to be excluded.
+    fn step(&mut self) {
+        let next = self.current.iter()
+            .flat_map(|pos| Vec2::DIRECTIONS.map(|direction| *pos + direction))
+            .filter(|pos| !self.previous.contains(pos) && self.is_plot(pos))
+            .collect::<HashSet<_>>();
 
-    for direction in &Vec2::DIRECTIONS {
-        let next_position = current_position + *direction;
+        self.reached.push(next.len() as u64);
+        self.previous = mem::replace(&mut self.current, next);
+    }
 
-        if let Some(Tile::Plot) = map.get(&next_position) {
-            result.append(&mut solve_rec(map, cache, next_position, steps_remaining - 1));
+    /// Number of plots the elf can be on after exactly `steps` steps.
+    fn reachable(&mut self, steps: usize) -> u64 {
+        while self.reached.len() <= steps {
+            self.step();
         }
+
+        self.reached[..=steps].iter()
+            .skip(steps % 2)
+            .step_by(2)
+            .sum()
     }
+}
 
-    cache.insert(key);
+struct Part2;
 
-    result
-}
+impl Part<u64, Map> for Part2 {
+    fn expect_test(&self) -> u64 {
+        167004
+    }
 
-fn solve_rec2(map: &Map, cache: &mut HashSet<(Vec2, usize)>, current_position: Vec2, steps_remaining: usize) -> Vec<Vec2> {
-    if steps_remaining == 0 {
-        return vec![current_position];
+    fn params(&self) -> Params {
+        Params::default().with("steps", 26501365)
     }
 
-    let key = (current_position, steps_remaining);
+    fn test_params(&self) -> Params {
+        Params::default().with("steps", 500)
+    }
 
-    if cache.contains(&key) {
-        return vec![];
+    fn test_cases(&self) -> Vec<TestCase<u64>> {
+        vec![
+            TestCase::new("21_test.txt", 50).with_param("steps", 10),
+            TestCase::new("21_test.txt", 1594).with_param("steps", 50),
+            TestCase::new("21_test.txt", 6536).with_param("steps", 100),
+        ]
     }
 
-    let mut result = vec![];
+    /// Once the walk has spread over a few copies of the map, it grows by the same pattern with every map width,
+    /// so the reachable plots sampled every map width grow quadratically. Walks until the samples settle into a
+    /// quadratic and extrapolates from there, or until the steps are reached if that happens first.
+    fn solve(&self, map: &Map) -> Result<u64, Error> {
+        let steps: usize = param("steps")?;
+        let period = map.width();
 
-    for direction in &Vec2::DIRECTIONS {
-        let next_position = current_position + *direction;
+        let mut walk = Walk::new(map, true);
+        let mut samples = vec![];
 
-        if let Tile::Plot = map.get_infinite(&next_position) {
-            result.append(&mut solve_rec2(map, cache, next_position, steps_remaining - 1));
-        }
-    }
+        for n in 0..=steps / period {
+            check_cancelled()?;
+            samples.push(walk.reachable(steps % period + n * period) as i64);
 
-    cache.insert(key);
+            if let Some(answer) = extrapolate(&samples, steps / period) {
+                return Ok(answer as u64);
+            }
+        }
 
-    result
+        Ok(*samples.last().context("no steps taken")? as u64)
+    }
 }
 
-struct Part2;
+/// How many samples in a row must have the same second difference before they are taken to be quadratic.
+const SETTLED_SAMPLES: usize = 4;
 
-impl Part<u64> for Part2 {
-    fn expect_test(&self) -> u64 {
-        167004
-    }
+/// Extrapolates the samples to the `target`th one, if their last second differences agree.
+fn extrapolate(samples: &[i64], target: usize) -> Option<i64> {
+    let n = samples.len();
 
-    fn solve(&self, input: &[String]) -> u64 {
-        let map = Map::from(input);
+    if n < SETTLED_SAMPLES + 2 {
+        return None;
+    }
 
-        let steps: usize = if map.width() < 12 {
-            500
-        } else {
-            26501365
-        };
+    let second_differences = samples.windows(3).map(|w| w[2] - 2 * w[1] + w[0]).collect::<Vec<_>>();
+    let settled = &second_differences[second_differences.len() - SETTLED_SAMPLES..];
 
-        let vec = solve_rec2(&map, &mut HashSet::new(), map.starting_position, steps);
+    if settled.iter().any(|d| *d != settled[0]) {
+        return None;
+    }
 
-        let set = vec.into_iter().collect::<HashSet<_>>();
+    let (last, first_difference, second_difference) = (samples[n - 1], samples[n - 1] - samples[n - 2], settled[0]);
+    let m = (target - (n - 1)) as i64;
 
-        set.len() as u64
-    }
+    Some(last + m * first_difference + m * (m + 1) / 2 * second_difference)
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -112,13 +158,13 @@ enum Tile {
 }
 
 impl TryFrom<char> for Tile {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
             '.' | 'S' => Ok(Self::Plot),
             '#' => Ok(Self::Rock),
-            _ => Err(())
+            _ => Err(Error::new(format!("invalid tile '{}'", value)))
         }
     }
 }
@@ -157,8 +203,10 @@ impl Map {
     }
 }
 
-impl From<&[String]> for Map {
-    fn from(value: &[String]) -> Self {
+impl TryFrom<&[String]> for Map {
+    type Error = Error;
+
+    fn try_from(value: &[String]) -> Result<Self, Self::Error> {
         let mut starting_position = None;
 
         let raw =
@@ -173,15 +221,16 @@ impl From<&[String]> for Map {
                                 starting_position = Some(v(x as i32, y as i32));
                             }
 
-                            c.try_into().unwrap()
+                            c.try_into()
                         })
-                        .collect()
+                        .collect::<Result<_, Error>>()
+                        .map_err(|e| e.at_line(y + 1))
                 )
-                .collect();
+                .collect::<Result<_, _>>()?;
 
-        let starting_position = starting_position.unwrap();
+        let starting_position = starting_position.context("missing starting position 'S'")?;
 
-        Self { raw, starting_position }
+        Ok(Self { raw, starting_position })
     }
 }
 
@@ -207,10 +256,6 @@ impl Vec2 {
     const fn new(x: i32, y: i32) -> Self {
         Self { x, y }
     }
-
-    const fn abs(&self) -> i32 {
-        self.x.abs() + self.y.abs()
-    }
 }
 
 impl Add<Vec2> for Vec2 {
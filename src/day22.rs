@@ -3,63 +3,66 @@ use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::harness::{Day, Part};
+use crate::harness::{Day, Error, parse_lines, Part, Registration};
 
-pub fn day22() -> Day<u32, u32> {
-    Day::new(22, Box::new(Part1 {}), Box::new(Part2 {}))
+pub const REGISTRATION: Registration = Registration::new(22, "Sand Slabs", || day22().f());
+
+fn day22() -> Day<u32, u32, Vec<Cuboid>> {
+    Day::with_parser(22, parse_settled, Box::new(Part1 {}), Box::new(Part2 {}))
+}
+
+/// Parses the bricks and lets them fall into place, which both parts start from.
+fn parse_settled(input: &[String]) -> Result<Vec<Cuboid>, Error> {
+    let mut bricks = parse(input)?;
+    drop_bricks(&mut bricks);
+    Ok(bricks)
 }
 
 struct Part1;
 
-impl Part<u32> for Part1 {
+impl Part<u32, Vec<Cuboid>> for Part1 {
     fn expect_test(&self) -> u32 {
         5
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
-        let mut bricks = parse(input);
-        drop_bricks(&mut bricks);
-
-        bricks.iter()
+    fn solve(&self, bricks: &Vec<Cuboid>) -> Result<u32, Error> {
+        Ok(bricks.iter()
             .filter(|&brick| {
                 let mut x = bricks.clone();
                 x.retain(|e| e != brick);
                 is_stable(&mut x)
             })
-            .count() as u32
+            .count() as u32)
     }
 }
 
 struct Part2;
 
-impl Part<u32> for Part2 {
+impl Part<u32, Vec<Cuboid>> for Part2 {
     fn expect_test(&self) -> u32 {
         7
     }
 
-    fn solve(&self, input: &[String]) -> u32 {
-        let mut bricks = parse(input);
-        drop_bricks(&mut bricks);
-
-        bricks.iter()
+    fn solve(&self, bricks: &Vec<Cuboid>) -> Result<u32, Error> {
+        Ok(bricks.iter()
             .map(|brick| {
                 let mut x = bricks.clone();
                 x.retain(|e| e != brick);
                 drop_bricks(&mut x)
             })
-            .sum::<usize>() as u32
+            .sum::<usize>() as u32)
     }
 }
 
-fn is_stable(vec: &mut Vec<Cuboid>) -> bool {
+fn is_stable(vec: &mut [Cuboid]) -> bool {
     _drop_bricks(vec, true) == 0
 }
 
-fn drop_bricks(vec: &mut Vec<Cuboid>) -> usize {
+fn drop_bricks(vec: &mut [Cuboid]) -> usize {
     _drop_bricks(vec, false)
 }
 
-fn _drop_bricks(vec: &mut Vec<Cuboid>, short_circuit: bool) -> usize {
+fn _drop_bricks(vec: &mut [Cuboid], short_circuit: bool) -> usize {
     let mut bricks_that_fell = HashSet::new();
 
     loop {
@@ -104,16 +107,12 @@ fn _drop_bricks(vec: &mut Vec<Cuboid>, short_circuit: bool) -> usize {
 }
 
 
-fn parse(input: &[String]) -> Vec<Cuboid> {
-    let mut result =
-        input.iter()
-            .filter(|e| !e.is_empty())
-            .map(|e| Cuboid::from_str(e).unwrap())
-            .collect::<Vec<_>>();
+fn parse(input: &[String]) -> Result<Vec<Cuboid>, Error> {
+    let mut result = parse_lines(input, Cuboid::from_str)?;
 
     result.sort_by_key(|e| e.max.z);
 
-    result
+    Ok(result)
 }
 
 static NEXT_CUBOID_ID: AtomicUsize = AtomicUsize::new(0);
@@ -147,21 +146,28 @@ impl Cuboid {
 }
 
 impl FromStr for Cuboid {
-    type Err = ();
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut vecs =
+        let vecs =
             s.split('~')
                 .map(|g| {
-                    let mut coords =
+                    let coords =
                         g.split(',')
-                            .map(|e| e.parse::<i32>().unwrap());
-
-                    v(coords.next().unwrap(), coords.next().unwrap(), coords.next().unwrap())
-                });
+                            .map(|e| e.parse::<i32>())
+                            .collect::<Result<Vec<_>, _>>()?;
 
+                    match coords[..] {
+                        [x, y, z] => Ok(v(x, y, z)),
+                        _ => Err(Error::new(format!("expected three coordinates in '{}'", g))),
+                    }
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
 
-        Ok(Self::new(vecs.next().unwrap(), vecs.next().unwrap()))
+        match vecs[..] {
+            [vec1, vec2] => Ok(Self::new(vec1, vec2)),
+            _ => Err(Error::new(format!("expected two corners separated by '~' in '{}'", s))),
+        }
     }
 }
 
@@ -0,0 +1,12 @@
+//! Example inputs compiled into the binary when it is built with the `embed-examples` feature,
+//! taken from the input directory at build time. Actual inputs are always read from disk.
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+/// The embedded content of an example input, by its file name such as `01_test1.txt`.
+pub fn embedded(file: &str) -> Option<&'static str> {
+    EMBEDDED.iter()
+        .find(|(name, _)| *name == file)
+        .map(|(_, content)| *content)
+}
+
@@ -1,142 +1,995 @@
 #![allow(dead_code)]
 
-use std::fmt::Debug;
-use std::fs;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter};
+use std::{env, fs, io};
+use std::num::ParseIntError;
 use std::path::Path;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
-use colored::Colorize;
+use crate::alloc::AllocStats;
+use crate::answers::Answers;
+use crate::bench;
+use crate::bench::{DayBench, Stats};
+use crate::cache::{AnswerCache, CachedAnswer};
+use crate::examples;
+use crate::report::{print_error, Recorder};
+use crate::worker;
+use crate::worker::catch_panic;
 
-pub trait AocResult: Debug + PartialEq + Sized {}
+/// The result of a part, which knows how to show itself as an [Answer].
+pub trait AocResult: Debug + PartialEq + Sized + Send + 'static {
+    fn answer(&self) -> Answer;
+}
+
+/// An answer as it is shown, recorded and pasted into the puzzle page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(String),
+    Text(String),
+    /// Several lines, such as letters drawn in ASCII art.
+    MultiLine(Vec<String>),
+    /// The part has not been solved yet, so it is skipped instead of checked.
+    NotImplemented,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(s) | Answer::Text(s) => write!(f, "{}", s),
+            Answer::MultiLine(lines) => write!(f, "{}", lines.join("\n")),
+            Answer::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+macro_rules! number_results {
+    ($($t:ty),*) => {
+        $(impl AocResult for $t {
+            fn answer(&self) -> Answer {
+                Answer::Number(self.to_string())
+            }
+        })*
+    };
+}
 
-impl<T: Debug + PartialEq> AocResult for T {}
+number_results!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
-pub trait Part<R: AocResult> {
+impl AocResult for String {
+    fn answer(&self) -> Answer {
+        Answer::Text(self.clone())
+    }
+}
+
+impl AocResult for &'static str {
+    fn answer(&self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+/// A result spanning several lines, such as letters drawn in ASCII art.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLine(pub Vec<String>);
+
+impl AocResult for MultiLine {
+    fn answer(&self) -> Answer {
+        Answer::MultiLine(self.0.clone())
+    }
+}
+
+/// The result of a part that has not been solved yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotImplemented;
+
+impl AocResult for NotImplemented {
+    fn answer(&self) -> Answer {
+        Answer::NotImplemented
+    }
+}
+
+/// One part of a day, solving the input as parsed by the day. Without a parser that is the raw lines.
+pub trait Part<R: AocResult, I: ?Sized = [String]>: Send + Sync {
+    /// Expected answer for the day's default test input, `NN_testK.txt` or `NN_test.txt`.
     fn expect_test(&self) -> R;
-    fn solve(&self, input: &[String]) -> R;
+
+    /// Parameters of the actual input, such as a number of steps, read by the part with [param].
+    /// Examples use them too unless they set their own.
+    fn params(&self) -> Params {
+        Params::default()
+    }
+
+    /// Parameters of the default test input where they differ from those of the actual input.
+    fn test_params(&self) -> Params {
+        Params::default()
+    }
+
+    /// Additional examples beyond the default test input.
+    fn test_cases(&self) -> Vec<TestCase<R>> {
+        vec![]
+    }
+
+    /// Alternative implementations, checked on every example and, with `--variants`, compared with `solve` on the actual input.
+    fn variants(&self) -> Vec<Variant<R, I>> {
+        vec![]
+    }
+
+    fn solve(&self, input: &I) -> Result<R, Error>;
+}
+
+/// A named alternative implementation of a part.
+pub struct Variant<R: AocResult, I: ?Sized = [String]> {
+    name: &'static str,
+    solve: fn(&I) -> Result<R, Error>,
+}
+
+impl<R: AocResult, I: ?Sized> Variant<R, I> {
+    pub fn new(name: &'static str, solve: fn(&I) -> Result<R, Error>) -> Self {
+        Self { name, solve }
+    }
+}
+
+pub struct TestCase<R: AocResult> {
+    file: String,
+    expected: R,
+    params: Params,
+}
+
+impl<R: AocResult> TestCase<R> {
+    /// Creates a test case for a file in the input directory.
+    pub fn new(file: &str, expected: R) -> Self {
+        Self { file: file.to_string(), expected, params: Params::default() }
+    }
+
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params = self.params.with(name, value);
+        self
+    }
+
+    fn with_params(mut self, params: Params) -> Self {
+        self.params = self.params.merged(&params);
+        self
+    }
+
+    fn name(&self) -> String {
+        if self.params.is_empty() {
+            self.file.clone()
+        } else {
+            format!("{} ({})", self.file, self.params)
+        }
+    }
+}
+
+/// Named values a part reads instead of guessing them from its input, such as the number of steps to simulate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn with(mut self, name: &str, value: impl ToString) -> Self {
+        self.values.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// These parameters, with those in `overrides` replacing any of the same name.
+    pub fn merged(&self, overrides: &Params) -> Params {
+        let mut values = self.values.clone();
+        values.extend(overrides.values.clone());
+        Params { values }
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, Error> {
+        let value = self.values.get(name).context(&format!("missing parameter '{}'", name))?;
+
+        value.parse().map_err(|_| Error::new(format!("invalid value '{}' for parameter '{}'", value, name)))
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let params = self.values.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<_>>();
+        write!(f, "{}", params.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Failed,
+    /// The part was abandoned after running for the given time.
+    Timeout(Duration),
+    /// The part panicked, the message holds the panic's message and location.
+    Panic,
+    /// The input file does not exist.
+    MissingInput,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    day: Option<u8>,
+    part: Option<u8>,
+    file: Option<String>,
+    line: Option<usize>,
 }
 
-trait Run<R: AocResult> {
-    fn run_test(&self, id: u8, input: &[String]) -> Duration;
-    fn run_actual(&self, id: u8, input: &[String]) -> Duration;
-    fn run_all(&self, id: u8, test_input: &[String], actual_input: &[String]) -> Duration;
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { kind: ErrorKind::Failed, message: message.into(), day: None, part: None, file: None, line: None }
+    }
+
+    pub fn timeout(elapsed: Duration) -> Self {
+        Self { kind: ErrorKind::Timeout(elapsed), ..Self::new(format!("timed out after {:.2?}", elapsed)) }
+    }
+
+    pub fn panic(message: impl Into<String>) -> Self {
+        Self { kind: ErrorKind::Panic, ..Self::new(message) }
+    }
+
+    pub fn missing_input(message: impl Into<String>) -> Self {
+        Self { kind: ErrorKind::MissingInput, ..Self::new(message) }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Attaches a 1-based line number, keeping the innermost one if already set.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    /// Offsets the line number for errors raised while parsing a sub-slice of the input.
+    pub fn shift_line(mut self, offset: usize) -> Self {
+        self.line = self.line.map(|line| line + offset);
+        self
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+
+    pub(crate) fn in_part(mut self, day: u8, part: u8) -> Self {
+        self.day.get_or_insert(day);
+        self.part.get_or_insert(part);
+        self
+    }
 }
 
-impl<T: Part<R> + ?Sized, R: AocResult> Run<R> for T {
-    fn run_test(&self, id: u8, input: &[String]) -> Duration {
-        let (actual, duration) = timed(|| { self.solve(input) });
-        let expected = self.expect_test();
-        assert_eq!(actual, expected, "Part {} test failed after {:?}: Expected {:?} but got {:?}", id, duration, expected, actual);
-        println!("Part {} test was {} {:>10}", id, "successful".on_bright_green(), format!("{:?}", duration).purple());
-        duration
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "Day{:0>2} ", day)?;
+        }
+
+        if let Some(part) = self.part {
+            write!(f, "part {} ", part)?;
+        }
+
+        match (&self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "at {}:{}: ", file, line)?,
+            (Some(file), None) => write!(f, "in {}: ", file)?,
+            (None, Some(line)) => write!(f, "at line {}: ", line)?,
+            (None, None) => {}
+        }
+
+        write!(f, "{}", self.message)
     }
+}
+
+impl std::error::Error for Error {}
 
-    fn run_actual(&self, id: u8, input: &[String]) -> Duration {
-        let (actual, duration) = timed(|| { self.solve(input) });
-        println!("{}", format!("Part {} output {:>12} {:>10}", id, format!("{:?}", actual).blue(), format!("{:?}", duration).purple()).on_blue());
-        duration
+impl From<ParseIntError> for Error {
+    fn from(value: ParseIntError) -> Self {
+        Error::new(value.to_string())
     }
+}
 
-    fn run_all(&self, id: u8, test_input: &[String], actual_input: &[String]) -> Duration {
-        self.run_test(id, test_input);
-        self.run_actual(id, actual_input)
+pub trait Context<T> {
+    fn context(self, message: &str) -> Result<T, Error>;
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context(self, message: &str) -> Result<T, Error> {
+        self.ok_or_else(|| Error::new(message))
     }
 }
 
+/// Parses every non-empty line, attaching the line number to any error.
+pub fn parse_lines<'a, T, F: Fn(&'a str) -> Result<T, Error>>(input: &'a [String], f: F) -> Result<Vec<T>, Error> {
+    input.iter()
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty())
+        .map(|(idx, l)| f(l).map_err(|e| e.at_line(idx + 1)))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Verdict {
+    #[default]
+    Unchecked,
+    Correct,
+    Regressed { expected: String },
+    /// The part is not implemented yet.
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub name: String,
+    pub expected: String,
+    pub actual: Option<String>,
+    pub duration: Option<Duration>,
+    pub error: Option<Error>,
+    pub passed: bool,
+    /// The part or the expected answer is not implemented yet.
+    pub skipped: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PartResult {
+    pub tests: Vec<TestResult>,
+    /// Time spent parsing the actual input, shared with the other part.
+    pub parse_duration: Option<Duration>,
+    /// Time spent solving the parsed actual input.
+    pub duration: Option<Duration>,
+    /// Allocations made while solving the actual input, if allocation tracking is enabled.
+    pub alloc: Option<AllocStats>,
+    pub answer: Option<String>,
+    pub error: Option<Error>,
+    pub verdict: Verdict,
+    /// Results of the part's variants on the actual input, if they were run.
+    pub variants: Vec<VariantResult>,
+    /// The answer and duration were taken from the cache of an earlier run instead of solving again.
+    pub cached: bool,
+    /// Whether the actual input was run, or the part failed before it could be. It isn't with `--test-only`.
+    pub ran_actual: bool,
+}
+
+impl PartResult {
+    pub fn regressed(&self) -> bool {
+        matches!(self.verdict, Verdict::Regressed { .. })
+    }
+
+    pub fn variants_disagree(&self) -> bool {
+        self.variants.iter().any(|variant| !variant.agrees)
+    }
+
+    /// Whether the part or one of its examples failed to produce an answer, or an example got the wrong one.
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || self.tests.iter().any(|test| !test.passed && !test.skipped)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantResult {
+    pub name: String,
+    pub answer: Option<String>,
+    pub duration: Option<Duration>,
+    pub error: Option<Error>,
+    /// Whether the variant found the same answer as the part itself.
+    pub agrees: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    All,
+    TestOnly,
+    ActualOnly,
+}
+
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+/// Path of the actual input that reads it from stdin instead.
+pub const STDIN_PATH: &str = "-";
+
+static INPUT_DIR: OnceLock<String> = OnceLock::new();
+
+/// Sets the directory the inputs, answers and baseline are read from. Only the first call has an effect.
+pub fn set_input_dir(dir: &str) {
+    let _ = INPUT_DIR.set(dir.to_string());
+}
+
+pub fn input_dir() -> &'static str {
+    INPUT_DIR.get_or_init(|| DEFAULT_INPUT_DIR.to_string())
+}
+
+/// Path of a file in the input directory.
+pub fn input_path(file: &str) -> String {
+    Path::new(input_dir()).join(file).to_string_lossy().into_owned()
+}
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Which parts and inputs of a day to run.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub part: Option<u8>,
+    pub mode: Mode,
+    /// Overrides the path of the actual input.
+    pub input: Option<String>,
+    /// How long a part may run before it is reported as timed out, see [worker::run_on_solver_thread].
+    pub timeout: Duration,
+    /// Also run the variants of each part on the actual input.
+    pub variants: bool,
+    /// Overrides parameters of the actual input, the examples keep their own.
+    pub params: Params,
+    /// Answers of earlier runs on the actual inputs to reuse, new answers are added to it.
+    pub cache: Option<Arc<Mutex<AnswerCache>>>,
+    /// Solve the actual inputs even if their answers are cached, replacing the cached answers.
+    pub fresh: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self { part: None, mode: Mode::All, input: None, timeout: DEFAULT_TIMEOUT, variants: false, params: Params::default(), cache: None, fresh: false }
+    }
+}
+
+impl RunOptions {
+    /// Whether the recorded answers belong to the actual inputs as run, which they don't if `--input` replaces them
+    /// or `--param` changes the question.
+    pub fn uses_recorded_inputs(&self) -> bool {
+        self.input.is_none() && self.params.is_empty()
+    }
+}
+
+/// Receives results as they are produced. The colored terminal output is one implementation.
+pub trait Reporter {
+    fn day_started(&mut self, _day: u8, _title: &str) {}
+    fn test_finished(&mut self, _day: u8, _part: u8, _result: &TestResult) {}
+    fn part_finished(&mut self, _day: u8, _part: u8, _result: &PartResult) {}
+    fn finish(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+trait Run<R: AocResult, I: ?Sized> {
+    fn run_test(&self, day: u8, id: u8, tests: &[(&TestCase<R>, &Parsed<I>)], options: &RunOptions, reporter: &mut dyn Reporter) -> Vec<TestResult>;
+    fn run_actual(&self, day: u8, id: u8, input: &Parsed<I>, expected: Option<&str>, options: &RunOptions) -> PartResult;
+    fn run_variants(&self, day: u8, id: u8, input: &Parsed<I>, answer: &str, params: &Params, timeout: Duration) -> Vec<VariantResult>;
+    fn run_all(&self, day: u8, id: u8, tests: &[(&TestCase<R>, &Parsed<I>)], actual: Option<(&Parsed<I>, Option<&str>)>, options: &RunOptions, reporter: &mut dyn Reporter) -> PartResult;
+    fn run_bench(&self, day: u8, id: u8, input: &Parsed<I>, params: &Params, warmup: usize, runs: usize) -> Option<Stats>;
+}
+
+impl<R: AocResult, I: ?Sized + Send + Sync + 'static> Run<R, I> for Arc<dyn Part<R, I>> {
+    /// Runs every example, once for the part and, if enabled, once for each variant.
+    fn run_test(&self, day: u8, id: u8, tests: &[(&TestCase<R>, &Parsed<I>)], options: &RunOptions, reporter: &mut dyn Reporter) -> Vec<TestResult> {
+        let variants = if options.variants { self.variants() } else { vec![] };
+
+        tests.iter()
+            .flat_map(|(case, input)| {
+                let part = self.clone();
+                let params = self.params().merged(&case.params);
+
+                std::iter::once((case.name(), solve(move |parsed| part.solve(parsed), day, id, input, params.clone(), options.timeout)))
+                    .chain(variants.iter().map(|variant| (format!("{} [{}]", case.name(), variant.name), solve(variant.solve, day, id, input, params.clone(), options.timeout))))
+                    .map(|(name, result)| {
+                        let expected = case.expected.answer();
+
+                        let result = match result {
+                            Ok((actual, duration, _)) => {
+                                let skipped = expected == Answer::NotImplemented || actual.answer() == Answer::NotImplemented;
+
+                                TestResult {
+                                    name,
+                                    skipped,
+                                    expected: expected.to_string(),
+                                    passed: !skipped && actual == case.expected,
+                                    actual: Some(actual.answer().to_string()),
+                                    duration: Some(duration),
+                                    error: None,
+                                }
+                            }
+                            Err(e) => TestResult { name, expected: expected.to_string(), actual: None, duration: None, error: Some(e), passed: false, skipped: false },
+                        };
+
+                        reporter.test_finished(day, id, &result);
+
+                        result
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn run_actual(&self, day: u8, id: u8, input: &Parsed<I>, expected: Option<&str>, options: &RunOptions) -> PartResult {
+        let part = self.clone();
+        let params = self.params().merged(&options.params);
+        let cache_key = input.hash.map(|hash| hash_input(&[format!("{:016x}", hash), params.to_string()]));
+
+        let cache = options.cache.as_ref().zip(cache_key);
+        let hit = cache.filter(|_| !options.fresh).and_then(|(cache, key)| cache.lock().unwrap().get(day, id, key).cloned());
+        let cached = hit.is_some();
+
+        let (answer, duration, alloc) = match hit {
+            Some(hit) => (hit.answer, hit.duration, None),
+            None => {
+                let (actual, duration, alloc) = match solve(move |parsed| part.solve(parsed), day, id, input, params.clone(), options.timeout) {
+                    Ok(result) => result,
+                    Err(e) => return PartResult { error: Some(e), ..PartResult::default() },
+                };
+
+                if actual.answer() == Answer::NotImplemented {
+                    return PartResult { verdict: Verdict::Skipped, ..PartResult::default() };
+                }
+
+                let answer = actual.answer().to_string();
+
+                if let Some((cache, key)) = cache {
+                    cache.lock().unwrap().insert(day, id, key, CachedAnswer { answer: answer.clone(), duration });
+                }
+
+                (answer, duration, alloc)
+            }
+        };
+
+        let verdict = match expected {
+            None => Verdict::Unchecked,
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Regressed { expected: expected.to_string() },
+        };
+
+        let variants = if options.variants { self.run_variants(day, id, input, &answer, &params, options.timeout) } else { vec![] };
+
+        PartResult { parse_duration: input.duration, duration: Some(duration), alloc, answer: Some(answer), verdict, variants, cached, ..PartResult::default() }
+    }
+
+    fn run_variants(&self, day: u8, id: u8, input: &Parsed<I>, answer: &str, params: &Params, timeout: Duration) -> Vec<VariantResult> {
+        self.variants()
+            .into_iter()
+            .map(|variant| {
+                match solve(variant.solve, day, id, input, params.clone(), timeout) {
+                    Ok((actual, duration, _)) => {
+                        let actual = actual.answer().to_string();
+                        VariantResult { name: variant.name.to_string(), agrees: actual == answer, answer: Some(actual), duration: Some(duration), error: None }
+                    }
+                    Err(e) => VariantResult { name: variant.name.to_string(), answer: None, duration: None, error: Some(e), agrees: false },
+                }
+            })
+            .collect()
+    }
+
+    fn run_all(&self, day: u8, id: u8, tests: &[(&TestCase<R>, &Parsed<I>)], actual: Option<(&Parsed<I>, Option<&str>)>, options: &RunOptions, reporter: &mut dyn Reporter) -> PartResult {
+        let tests = self.run_test(day, id, tests, options, reporter);
+
+        let result = match actual {
+            Some((input, expected)) => PartResult { tests, ran_actual: true, ..self.run_actual(day, id, input, expected, options) },
+            None => PartResult { tests, ..PartResult::default() },
+        };
+
+        reporter.part_finished(day, id, &result);
+        result
+    }
+
+    /// Solves the part repeatedly on the already parsed input with the params overridden, each run on its own solver thread like [Run::run_actual].
+    /// Only the time spent in [Part::solve] is measured.
+    fn run_bench(&self, day: u8, id: u8, input: &Parsed<I>, params: &Params, warmup: usize, runs: usize) -> Option<Stats> {
+        let params = self.params().merged(params);
+
+        let sample = || {
+            let part = self.clone();
+            solve(move |parsed| part.solve(parsed), day, id, input, params.clone(), DEFAULT_TIMEOUT).map(|(_, duration, _)| duration)
+        };
+
+        bench::measure(sample, warmup, runs).map_err(|e| print_error(&e)).ok()
+    }
+}
+
+/// Solves a part, or one of its variants, on the parsed input, attaching day, part and file context to any error.
+/// The solver runs on its own thread, see [worker::run_on_solver_thread].
+fn solve<R: AocResult, I: ?Sized + Send + Sync + 'static>(
+    solver: impl FnOnce(&I) -> Result<R, Error> + Send + 'static,
+    day: u8,
+    id: u8,
+    input: &Parsed<I>,
+    params: Params,
+    timeout: Duration,
+) -> Result<(R, Duration, Option<AllocStats>), Error> {
+    input.result.clone()
+        .and_then(|parsed| worker::run_on_solver_thread(format!("day{:0>2}-part{}", day, id), timeout, move || with_params(params, || solver(&parsed))))
+        .map_err(|e| e.in_file(&input.path).in_part(day, id))
+}
+
+thread_local! {
+    /// Parameters of the input the part on this thread is solving.
+    static PARAMS: RefCell<Params> = RefCell::new(Params::default());
+    /// Set once the part on this thread has timed out and nobody waits for its result anymore.
+    static CANCELLED: RefCell<Arc<AtomicBool>> = RefCell::new(Arc::default());
+}
+
+/// Reads a parameter of the input being solved, as set by the part, the test case or `--param`.
+pub fn param<T: FromStr>(name: &str) -> Result<T, Error> {
+    PARAMS.with_borrow(|params| params.get(name))
+}
+
+/// Fails once the part being solved has timed out. Loops that can run for long call it with `?` to stop early,
+/// see [worker::run_on_solver_thread].
+pub fn check_cancelled() -> Result<(), Error> {
+    if CANCELLED.with_borrow(|cancelled| cancelled.load(Ordering::Relaxed)) {
+        return Err(Error::new("cancelled after timing out"));
+    }
+
+    Ok(())
+}
+
+/// Sets the flag that makes [check_cancelled] fail on this thread.
+pub(crate) fn set_cancel_flag(cancelled: Arc<AtomicBool>) {
+    CANCELLED.set(cancelled);
+}
+
+/// Runs `f` with the parameters that [param] reads on this thread.
+fn with_params<R>(params: Params, f: impl FnOnce() -> R) -> R {
+    let previous = PARAMS.replace(params);
+    let result = f();
+    PARAMS.set(previous);
+    result
+}
+
 pub struct EmptyPart {}
 
-const NOT_IMPLEMENTED: &str = "NOT_IMPLEMENTED";
+impl Part<NotImplemented> for EmptyPart {
+    fn expect_test(&self) -> NotImplemented {
+        NotImplemented
+    }
+
+    fn solve(&self, _: &[String]) -> Result<NotImplemented, Error> {
+        Ok(NotImplemented)
+    }
+}
+
+struct Input {
+    path: String,
+    lines: Result<Vec<String>, Error>,
+}
+
+impl Input {
+    fn read(path: String) -> Self {
+        if path == STDIN_PATH {
+            return Self { path: "<stdin>".to_string(), lines: read_stdin() };
+        }
 
-impl Part<&str> for EmptyPart {
-    fn expect_test(&self) -> &'static str {
-        NOT_IMPLEMENTED
+        let lines = read_input(&path);
+        Self { path, lines }
     }
 
-    fn solve(&self, _: &[String]) -> &'static str {
-        NOT_IMPLEMENTED
+    /// Reads an example input from the input directory, falling back to the copy embedded in the binary.
+    fn read_example(file: &str) -> Self {
+        let path = input_path(file);
+
+        match examples::embedded(file) {
+            Some(content) if !Path::new(&path).is_file() => Self { path: format!("<embedded {}>", file), lines: Ok(split_lines(content)) },
+            _ => Self::read(path),
+        }
+    }
+
+    fn parse<I: ?Sized>(&self, parse: &ParseFn<I>) -> Parsed<I> {
+        let (result, duration) = match &self.lines {
+            Ok(lines) => {
+                let (result, duration) = timed(|| catch_panic(|| parse(lines)).map(Arc::from));
+                (result, Some(duration))
+            }
+            Err(e) => (Err(e.clone()), None),
+        };
+
+        let hash = self.lines.as_ref().ok().map(|lines| hash_input(lines));
+
+        Parsed { path: self.path.clone(), result, duration, hash }
     }
 }
 
-pub struct Day<R1: AocResult, R2: AocResult> {
+/// An input after parsing, shared by every part and test that reads the same file.
+struct Parsed<I: ?Sized> {
+    path: String,
+    result: Result<Arc<I>, Error>,
+    duration: Option<Duration>,
+    /// Hash of the lines, if they could be read.
+    hash: Option<u64>,
+}
+
+type ParseFn<I> = Box<dyn Fn(&[String]) -> Result<Box<I>, Error>>;
+
+/// A day's parts and how to parse its input. Inputs are only read from the input directory when the day is run.
+pub struct Day<R1: AocResult, R2: AocResult, I: ?Sized = [String]> {
     id: u8,
-    test_input1: Vec<String>,
-    test_input2: Vec<String>,
-    actual_input: Vec<String>,
-    part1: Box<dyn Part<R1>>,
-    part2: Box<dyn Part<R2>>,
+    parse: ParseFn<I>,
+    part1: Arc<dyn Part<R1, I>>,
+    part2: Arc<dyn Part<R2, I>>,
 }
 
-impl<R1: AocResult + 'static, R2: AocResult + 'static> Day<R1, R2> {
+impl<R1: AocResult, R2: AocResult> Day<R1, R2> {
     pub fn new(id: u8, part1: Box<dyn Part<R1>>, part2: Box<dyn Part<R2>>) -> Self {
-        Self {
-            id,
-            test_input1: Self::read_test_input(id, 1),
-            test_input2: Self::read_test_input(id, 2),
-            actual_input: read_input(format!("input/{:0>2}.txt", id).as_str()),
-            part1,
-            part2,
-        }
+        Self::build(id, Box::new(|lines| Ok(lines.into())), part1, part2)
     }
+}
 
-    fn read_test_input(id: u8, test_index: usize) -> Vec<String> {
+impl<R1: AocResult, R2: AocResult, I: Send + Sync + 'static> Day<R1, R2, I> {
+    /// Creates a day whose input is parsed once with `parse` and then handed to both parts.
+    pub fn with_parser(id: u8, parse: fn(&[String]) -> Result<I, Error>, part1: Box<dyn Part<R1, I>>, part2: Box<dyn Part<R2, I>>) -> Self {
+        Self::build(id, Box::new(move |lines| parse(lines).map(Box::new)), part1, part2)
+    }
+}
+
+impl<R1: AocResult, R2: AocResult, I: ?Sized + Send + Sync + 'static> Day<R1, R2, I> {
+    fn build(id: u8, parse: ParseFn<I>, part1: Box<dyn Part<R1, I>>, part2: Box<dyn Part<R2, I>>) -> Self {
+        Self { id, parse, part1: Arc::from(part1), part2: Arc::from(part2) }
+    }
+
+    fn test_cases<R: AocResult>(id: u8, test_index: usize, part: &dyn Part<R, I>) -> Vec<TestCase<R>> {
+        let default_case = TestCase::new(&Self::get_default_test_input_file_name(id, test_index), part.expect_test()).with_params(part.test_params());
+
+        std::iter::once(default_case)
+            .chain(part.test_cases())
+            .collect()
+    }
+
+    fn read_tests<R: AocResult>(id: u8, test_index: usize, part: &dyn Part<R, I>) -> Vec<(TestCase<R>, Input)> {
+        Self::test_cases(id, test_index, part)
+            .into_iter()
+            .map(|case| {
+                let input = Input::read_example(&case.file);
+                (case, input)
+            })
+            .collect()
+    }
+
+    fn get_default_test_input_file_name(id: u8, test_index: usize) -> String {
         let test_input_name_with_id = Self::get_test_input_file_name(id, Some(test_index));
 
-        if Path::new(&test_input_name_with_id).is_file() {
-            read_input(&test_input_name_with_id)
+        if Path::new(&input_path(&test_input_name_with_id)).is_file() || examples::embedded(&test_input_name_with_id).is_some() {
+            test_input_name_with_id
         } else {
-            read_input(&Self::get_test_input_file_name(id, None))
+            Self::get_test_input_file_name(id, None)
         }
     }
 
     fn get_test_input_file_name(id: u8, test_id: Option<usize>) -> String {
-        format!("input/{:0>2}_test{}.txt", id, test_id.map(|i| i.to_string()).unwrap_or("".to_string()))
+        format!("{:0>2}_test{}.txt", id, test_id.map(|i| i.to_string()).unwrap_or("".to_string()))
     }
 
-    pub fn run_part1_test(&self) -> Duration {
-        self.part1.run_test(1, &self.test_input1)
+    fn actual_input_path(&self) -> String {
+        input_path(&format!("{:0>2}.txt", self.id))
     }
 
-    pub fn run_part2_test(&self) -> Duration {
-        self.part2.run_test(2, &self.test_input2)
+    /// Runs the selected parts, returning their results by part number.
+    /// Every input file is read and parsed at most once, even if both parts or several tests use it.
+    pub fn run(&self, answers: &Answers, options: &RunOptions, reporter: &mut dyn Reporter) -> Vec<(u8, PartResult)> {
+        let selected = |part: u8| options.part.is_none_or(|p| p == part);
+        let run_tests = |part: u8| selected(part) && options.mode != Mode::ActualOnly;
+
+        let tests1 = if run_tests(1) { Self::read_tests(self.id, 1, self.part1.as_ref()) } else { vec![] };
+        let tests2 = if run_tests(2) { Self::read_tests(self.id, 2, self.part2.as_ref()) } else { vec![] };
+
+        let actual_input = Some(Input::read(options.input.clone().unwrap_or_else(|| self.actual_input_path())))
+            .filter(|_| options.mode != Mode::TestOnly);
+
+        let inputs = tests1.iter()
+            .map(|(_, input)| input)
+            .chain(tests2.iter().map(|(_, input)| input))
+            .chain(&actual_input);
+
+        let mut parsed: HashMap<&str, Parsed<I>> = HashMap::new();
+
+        for input in inputs {
+            parsed.entry(&input.path).or_insert_with(|| input.parse(&self.parse));
+        }
+
+        let expected = |part: u8| answers.get(self.id, part).filter(|_| options.uses_recorded_inputs());
+        let actual = |part: u8| actual_input.as_ref().and_then(|input| parsed.get(input.path.as_str())).map(|parsed| (parsed, expected(part)));
+
+        let mut results = vec![];
+
+        if selected(1) {
+            results.push((1, self.part1.run_all(self.id, 1, &with_parsed(&tests1, &parsed), actual(1), options, reporter)));
+        }
+
+        if selected(2) {
+            results.push((2, self.part2.run_all(self.id, 2, &with_parsed(&tests2, &parsed), actual(2), options, reporter)));
+        }
+
+        results
     }
 
-    pub fn run_part1_actual(&self) -> Duration {
-        self.part1.run_actual(1, &self.actual_input)
+    /// Benchmarks both parts on the actual input, or the one at `input`, which is read and parsed once up front so neither is measured.
+    /// The params override those of the actual input like [RunOptions::params].
+    pub fn bench(&self, input: Option<&str>, params: &Params, warmup: usize, runs: usize) -> DayBench {
+        let actual_input = Input::read(input.map(str::to_string).unwrap_or_else(|| self.actual_input_path())).parse(&self.parse);
+
+        DayBench {
+            input_hash: actual_input.hash,
+            part1: self.part1.run_bench(self.id, 1, &actual_input, params, warmup, runs),
+            part2: self.part2.run_bench(self.id, 2, &actual_input, params, warmup, runs),
+        }
     }
 
-    pub fn run_part2_actual(&self) -> Duration {
-        self.part2.run_actual(2, &self.actual_input)
+    /// Solves one part on the given input, without reading anything from the input directory.
+    /// Returns the answer as it is pasted into the puzzle page, or an error if the part is not implemented.
+    pub fn solve(&self, part: u8, input: &str) -> Result<String, Error> {
+        let parsed = Input { path: "<input>".to_string(), lines: Ok(split_lines(input)) }.parse(&self.parse);
+
+        let (part1, part2) = (self.part1.clone(), self.part2.clone());
+
+        let answer = match part {
+            1 => solve(move |input| part1.solve(input), self.id, 1, &parsed, self.part1.params(), DEFAULT_TIMEOUT)?.0.answer(),
+            2 => solve(move |input| part2.solve(input), self.id, 2, &parsed, self.part2.params(), DEFAULT_TIMEOUT)?.0.answer(),
+            _ => return Err(Error::new(format!("invalid part '{}', expected 1 or 2", part))),
+        };
+
+        match answer {
+            Answer::NotImplemented => Err(Error::new("not implemented").in_part(self.id, part)),
+            answer => Ok(answer.to_string()),
+        }
     }
 
-    pub fn run(&self) -> (Duration, Duration) {
-        println!("~~~~~~~~{{ {} }} ~~~~~~~~", format!("Day{:0>2}", self.id).yellow());
-        (
-            self.part1.run_all(1, &self.test_input1, &self.actual_input),
-            self.part2.run_all(2, &self.test_input2, &self.actual_input),
-        )
+    /// Paths of the actual input and every test input, whether or not they exist.
+    fn input_paths(&self) -> Vec<String> {
+        let mut paths = Self::test_cases(self.id, 1, self.part1.as_ref()).iter()
+            .map(|case| input_path(&case.file))
+            .chain(Self::test_cases(self.id, 2, self.part2.as_ref()).iter().map(|case| input_path(&case.file)))
+            .chain([self.actual_input_path()])
+            .collect::<Vec<_>>();
+
+        paths.sort();
+        paths.dedup();
+
+        paths
     }
 
     pub fn f(self) -> DayRunner {
-        DayRunner::new(Box::new(move || self.run()))
+        let id = self.id;
+        let day = Rc::new(self);
+        let (bench_day, solve_day, paths_day) = (day.clone(), day.clone(), day.clone());
+
+        DayRunner {
+            id,
+            title: "",
+            f: Box::new(move |answers, options, reporter| day.run(answers, options, reporter)),
+            bench: Box::new(move |input, params, warmup, runs| bench_day.bench(input, params, warmup, runs)),
+            solve: Box::new(move |part, input| solve_day.solve(part, input)),
+            input_paths: Box::new(move || paths_day.input_paths()),
+        }
     }
 }
 
+/// Pairs each test case with its parsed input, leaving out tests whose input was not parsed.
+fn with_parsed<'a, R: AocResult, I: ?Sized>(tests: &'a [(TestCase<R>, Input)], parsed: &'a HashMap<&str, Parsed<I>>) -> Vec<(&'a TestCase<R>, &'a Parsed<I>)> {
+    tests.iter()
+        .filter_map(|(case, input)| parsed.get(input.path.as_str()).map(|parsed| (case, parsed)))
+        .collect()
+}
+
+type RunFn = Box<dyn Fn(&Answers, &RunOptions, &mut dyn Reporter) -> Vec<(u8, PartResult)>>;
+type BenchFn = Box<dyn Fn(Option<&str>, &Params, usize, usize) -> DayBench>;
+type SolveFn = Box<dyn Fn(u8, &str) -> Result<String, Error>>;
+type PathsFn = Box<dyn Fn() -> Vec<String>>;
+
 pub struct DayRunner {
-    pub f: Box<dyn Fn() -> (Duration, Duration)>,
+    pub id: u8,
+    pub title: &'static str,
+    pub f: RunFn,
+    pub bench: BenchFn,
+    /// Solves one part on an input given as a string.
+    pub solve: SolveFn,
+    /// Paths of the input files the day reads.
+    pub input_paths: PathsFn,
 }
 
 impl DayRunner {
-    pub fn new(f: Box<dyn Fn() -> (Duration, Duration)>) -> Self {
-        Self { f }
+
+    pub fn run(&self, answers: &Answers, options: &RunOptions, reporter: &mut dyn Reporter) -> Vec<(u8, PartResult)> {
+        reporter.day_started(self.id, self.title);
+        (self.f)(answers, options, reporter)
+    }
+}
+
+/// Checks the examples of one part and its variants for the generated `cargo test` cases, with the usual assertion messages.
+/// The example inputs are public and kept in the repository, so a missing one fails the test like a wrong answer.
+pub fn check_examples(registration: &Registration, part: u8) {
+    let options = RunOptions { part: Some(part), mode: Mode::TestOnly, variants: true, ..RunOptions::default() };
+    let results = registration.load().run(&Answers::default(), &options, &mut Recorder::default());
+
+    for test in results.iter().flat_map(|(_, result)| &result.tests) {
+        match &test.error {
+            None if test.skipped => eprintln!("Skipping example {}: not implemented", test.name),
+            Some(e) => panic!("{}", e),
+            None => assert_eq!(test.actual.as_deref().unwrap_or_default(), test.expected, "example {}", test.name),
+        }
+    }
+}
+
+/// A day known to the [Registry]. The day itself, including its inputs, is only constructed by [Registration::load].
+#[derive(Clone, Copy)]
+pub struct Registration {
+    pub id: u8,
+    pub title: &'static str,
+    constructor: fn() -> DayRunner,
+}
+
+impl Registration {
+    pub const fn new(id: u8, title: &'static str, constructor: fn() -> DayRunner) -> Self {
+        Self { id, title, constructor }
+    }
+
+    pub fn load(&self) -> DayRunner {
+        let runner = (self.constructor)();
+        assert_eq!(runner.id, self.id, "Day{:0>2} is registered with the constructor of Day{:0>2}", self.id, runner.id);
+        DayRunner { title: self.title, ..runner }
+    }
+}
+
+/// All days with a solution, keyed by id.
+pub struct Registry {
+    days: BTreeMap<u8, Registration>,
+}
+
+impl Registry {
+    pub fn new(registrations: Vec<Registration>) -> Self {
+        let mut days = BTreeMap::new();
+
+        for registration in registrations {
+            if days.insert(registration.id, registration).is_some() {
+                panic!("Day{:0>2} is registered twice", registration.id);
+            }
+        }
+
+        Self { days }
+    }
+
+    pub fn get(&self, id: u8) -> Result<&Registration, Error> {
+        self.days.get(&id).context(&format!("Day{:0>2} is not registered", id))
+    }
+
+    pub fn all(&self) -> impl Iterator<Item=&Registration> {
+        self.days.values()
+    }
+
+    pub fn latest(&self) -> Option<&Registration> {
+        self.days.values().next_back()
     }
 }
 
-fn timed<R: AocResult, F: Fn() -> R>(f: F) -> (R, Duration) {
+pub(crate) fn timed<R, F: FnOnce() -> R>(f: F) -> (R, Duration) {
     let start = Instant::now();
     let result = f();
     (result, start.elapsed())
 }
 
-fn read_input(path: &str) -> Vec<String> {
-    fs::read_to_string(path).unwrap().split('\n').map(String::from).collect::<Vec<_>>()
+fn split_lines(s: &str) -> Vec<String> {
+    s.split('\n').map(String::from).collect()
+}
+
+fn read_input(path: &str) -> Result<Vec<String>, Error> {
+    fs::read_to_string(path)
+        .map(|s| split_lines(&s))
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => {
+                let tried = env::current_dir().map(|dir| dir.join(path)).unwrap_or(path.into());
+                Error::missing_input(format!("input not found, tried {}", tried.display())).in_file(path)
+            }
+            _ => Error::new(format!("could not read input: {}", e)).in_file(path),
+        })
+}
+
+fn read_stdin() -> Result<Vec<String>, Error> {
+    io::read_to_string(io::stdin())
+        .map(|s| split_lines(&s))
+        .map_err(|e| Error::new(format!("could not read input from stdin: {}", e)))
+}
+
+/// FNV-1a hash of the input, stable across builds and platforms.
+pub fn hash_input(lines: &[String]) -> u64 {
+    lines.join("\n")
+        .bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
 }
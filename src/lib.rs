@@ -0,0 +1,93 @@
+//! Advent of Code 2023 solutions, and the harness that tests, runs and benchmarks them.
+
+use crate::harness::Registry;
+
+pub use crate::harness::Error;
+
+pub mod alloc;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod cli;
+pub mod commands;
+pub mod examples;
+pub mod harness;
+pub mod plot;
+pub mod report;
+pub mod scaffold;
+mod store;
+pub mod watch;
+pub mod worker;
+
+/// Declares the day modules and registers them. Adding a day only needs its module name here.
+/// Every day also gets a `cargo test` case per part, such as `tests::day17::part2_example`, checking its examples.
+macro_rules! days {
+    ($($day:ident),* $(,)?) => {
+        $(pub mod $day;)*
+
+        /// Every day with a solution.
+        pub fn registry() -> Registry {
+            Registry::new(vec![$($day::REGISTRATION),*])
+        }
+
+        #[cfg(test)]
+        mod tests {
+            $(
+                mod $day {
+                    use crate::harness::check_examples;
+
+                    #[test]
+                    fn part1_example() {
+                        check_examples(&crate::$day::REGISTRATION, 1);
+                    }
+
+                    #[test]
+                    fn part2_example() {
+                        check_examples(&crate::$day::REGISTRATION, 2);
+                    }
+                }
+            )*
+        }
+    };
+}
+
+days![
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10,
+    day11, day12, day13, day14, day15, day16, day17, day18, day19, day20,
+    day21, day22,
+];
+
+/// Solves one part of a day on a puzzle input, returning the answer as the runner prints it.
+/// Nothing is read from the filesystem, so the input directory does not need to exist.
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, Error> {
+    let registration = *registry().get(day)?;
+
+    (registration.load().solve)(part, input)
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+
+    #[test]
+    fn solves_a_part_without_the_input_directory() {
+        assert_eq!(solve(1, 1, EXAMPLE).unwrap(), "142");
+    }
+
+    #[test]
+    fn rejects_an_unregistered_day() {
+        assert_eq!(solve(25, 1, EXAMPLE).unwrap_err().to_string(), "Day25 is not registered");
+    }
+
+    #[test]
+    fn rejects_an_invalid_part() {
+        assert_eq!(solve(1, 3, EXAMPLE).unwrap_err().to_string(), "invalid part '3', expected 1 or 2");
+    }
+
+    #[test]
+    fn reports_errors_of_the_part() {
+        assert_eq!(solve(1, 1, "no digits here").unwrap_err().to_string(), "Day01 part 1 at <input>:1: line contains no digit");
+    }
+}
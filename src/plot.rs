@@ -0,0 +1,174 @@
+use std::fmt::Write;
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+use std::time::Duration;
+
+const PLOT_HEIGHT: f64 = 400.0;
+const MARGIN_LEFT: f64 = 70.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 50.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+const GROUP_WIDTH: f64 = 40.0;
+const BAR_WIDTH: f64 = 15.0;
+
+const PART1_COLOR: &str = "#4e79a7";
+const PART2_COLOR: &str = "#f28e2b";
+
+pub fn plot(path: &Path, part1: &[Option<Duration>], part2: &[Option<Duration>]) -> Result<(), Error> {
+    fs::write(path, render_svg(part1, part2))
+}
+
+/// Renders a log-scale bar chart with one group of two bars per day, days numbered from 1.
+/// Parts without a duration are left out.
+pub fn render_svg(part1: &[Option<Duration>], part2: &[Option<Duration>]) -> String {
+    let scale = LogScale::new(part1.iter().chain(part2).flatten().map(micros));
+
+    let days = part1.len().max(part2.len());
+    let plot_width = days as f64 * GROUP_WIDTH;
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+
+    let mut svg = String::new();
+
+    writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="sans-serif" font-size="12">"#, width, height, width, height).unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+    for exponent in scale.min_exponent..=scale.max_exponent {
+        let y = scale.y(10_f64.powi(exponent));
+        writeln!(svg, r##"<line x1="{}" y1="{:.1}" x2="{}" y2="{:.1}" stroke="#dddddd"/>"##, MARGIN_LEFT, y, MARGIN_LEFT + plot_width, y).unwrap();
+        writeln!(svg, r#"<text x="{}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#, MARGIN_LEFT - 6.0, y, format_decade(exponent)).unwrap();
+    }
+
+    for day in 0..days {
+        let group_x = MARGIN_LEFT + day as f64 * GROUP_WIDTH;
+        let center = group_x + GROUP_WIDTH / 2.0;
+
+        let bars = [(part1.get(day).copied().flatten(), center - BAR_WIDTH, PART1_COLOR), (part2.get(day).copied().flatten(), center, PART2_COLOR)];
+
+        for (duration, x, color) in bars {
+            if let Some(duration) = duration {
+                let y = scale.y(micros(&duration));
+                writeln!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{:.1}" width="{}" height="{:.1}" fill="{}"><title>Day {:0>2}: {:?}</title></rect>"#,
+                    x, y, BAR_WIDTH, bottom - y, color, day + 1, duration,
+                ).unwrap();
+            }
+        }
+
+        writeln!(svg, r#"<text x="{:.1}" y="{}" text-anchor="middle">{:0>2}</text>"#, center, bottom + 16.0, day + 1).unwrap();
+    }
+
+    writeln!(svg, r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#000000"/>"##, MARGIN_LEFT, MARGIN_TOP, MARGIN_LEFT, bottom).unwrap();
+    writeln!(svg, r##"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="#000000"/>"##, MARGIN_LEFT, bottom, MARGIN_LEFT + plot_width, bottom).unwrap();
+
+    writeln!(svg, r#"<text x="{}" y="{}" text-anchor="middle">Day</text>"#, MARGIN_LEFT + plot_width / 2.0, bottom + 36.0).unwrap();
+    writeln!(svg, r#"<text x="{}" y="{}" font-size="16" font-weight="bold">Runtime per day (log scale)</text>"#, MARGIN_LEFT, MARGIN_TOP - 30.0).unwrap();
+
+    for (i, (label, color)) in [("Part 1", PART1_COLOR), ("Part 2", PART2_COLOR)].into_iter().enumerate() {
+        let x = MARGIN_LEFT + i as f64 * 70.0;
+        writeln!(svg, r#"<rect x="{}" y="{}" width="12" height="12" fill="{}"/>"#, x, MARGIN_TOP - 22.0, color).unwrap();
+        writeln!(svg, r#"<text x="{}" y="{}">{}</text>"#, x + 16.0, MARGIN_TOP - 12.0, label).unwrap();
+    }
+
+    writeln!(svg, "</svg>").unwrap();
+
+    svg
+}
+
+fn micros(duration: &Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn format_decade(exponent: i32) -> String {
+    match exponent {
+        i32::MIN..=-1 => format!("{}ns", 10_u64.pow((exponent + 3) as u32)),
+        0..=2 => format!("{}µs", 10_u64.pow(exponent as u32)),
+        3..=5 => format!("{}ms", 10_u64.pow(exponent as u32 - 3)),
+        _ => format!("{}s", 10_u64.pow(exponent as u32 - 6)),
+    }
+}
+
+struct LogScale {
+    min_exponent: i32,
+    max_exponent: i32,
+}
+
+impl LogScale {
+    fn new(values: impl Iterator<Item=f64>) -> Self {
+        let (min, max) =
+            values
+                .map(|e| e.max(0.001))
+                .fold((f64::MAX, f64::MIN), |(min, max), e| (min.min(e), max.max(e)));
+
+        if min > max {
+            return Self { min_exponent: 0, max_exponent: 1 };
+        }
+
+        let min_exponent = min.log10().floor() as i32;
+        let max_exponent = (max.log10().ceil() as i32).max(min_exponent + 1);
+
+        Self { min_exponent, max_exponent }
+    }
+
+    fn y(&self, micros: f64) -> f64 {
+        let fraction = (micros.max(0.001).log10() - self.min_exponent as f64) / (self.max_exponent - self.min_exponent) as f64;
+
+        MARGIN_TOP + PLOT_HEIGHT * (1.0 - fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars(svg: &str) -> Vec<&str> {
+        svg.lines().filter(|line| line.contains("<title>")).collect()
+    }
+
+    #[test]
+    fn leaves_out_parts_without_a_duration() {
+        let part1 = [Some(Duration::from_micros(5)), None, Some(Duration::from_millis(20))];
+        let part2 = [None, Some(Duration::from_micros(300))];
+
+        let svg = render_svg(&part1, &part2);
+        let bars = bars(&svg);
+
+        assert_eq!(bars.len(), 3);
+        assert!(bars[0].contains("Day 01: 5µs") && bars[0].contains(PART1_COLOR));
+        assert!(bars[1].contains("Day 02: 300µs") && bars[1].contains(PART2_COLOR));
+        assert!(bars[2].contains("Day 03: 20ms") && bars[2].contains(PART1_COLOR));
+        assert!(svg.contains(">03</text>"));
+    }
+
+    #[test]
+    fn labels_every_decade_between_the_fastest_and_slowest_part() {
+        let svg = render_svg(&[Some(Duration::from_micros(5))], &[Some(Duration::from_millis(20))]);
+
+        let labels = svg.lines()
+            .filter(|line| line.contains(r#"text-anchor="end""#))
+            .map(|line| line.split('>').nth(1).unwrap().trim_end_matches("</text"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(labels, ["1µs", "10µs", "100µs", "1ms", "10ms", "100ms"]);
+    }
+
+    #[test]
+    fn formats_decades_in_the_nearest_unit() {
+        assert_eq!(format_decade(-3), "1ns");
+        assert_eq!(format_decade(-1), "100ns");
+        assert_eq!(format_decade(2), "100µs");
+        assert_eq!(format_decade(3), "1ms");
+        assert_eq!(format_decade(7), "10s");
+    }
+
+    #[test]
+    fn renders_an_empty_chart_without_durations() {
+        let svg = render_svg(&[None, None], &[]);
+
+        assert!(bars(&svg).is_empty());
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+    }
+}
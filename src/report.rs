@@ -0,0 +1,803 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::harness::{Error, ErrorKind, PartResult, Reporter, TestResult, VariantResult, Verdict};
+
+/// Prints each result as it comes in, and a tally of passed and failed checks at the end.
+#[derive(Default)]
+pub struct PrettyReporter {
+    passed: usize,
+    /// Answers to the actual input that there is no known correct answer for.
+    unchecked: usize,
+    skipped: usize,
+    failed: Vec<(u8, u8)>,
+}
+
+impl PrettyReporter {
+    fn count(&mut self, day: u8, part: u8, passed: bool) {
+        if passed {
+            self.passed += 1;
+        } else {
+            self.failed.push((day, part));
+        }
+    }
+}
+
+impl Reporter for PrettyReporter {
+    fn day_started(&mut self, day: u8, title: &str) {
+        println!("~~~~~~~~{{ {}: {} }} ~~~~~~~~", format!("Day{:0>2}", day).yellow(), title);
+    }
+
+    fn test_finished(&mut self, day: u8, part: u8, result: &TestResult) {
+        if result.skipped {
+            self.skipped += 1;
+            println!("Part {} test {} was {}", part, result.name, "skipped, not implemented".yellow());
+            return;
+        }
+
+        self.count(day, part, result.passed);
+
+        match (&result.error, &result.actual, result.duration) {
+            (Some(e), _, _) => print_error(e),
+            (None, Some(_), Some(duration)) if result.passed => {
+                println!("Part {} test {} was {} {:>10}", part, result.name, "successful".on_bright_green(), format!("{:?}", duration).purple());
+            }
+            (None, actual, duration) => {
+                println!("{}", format!("Part {} test {} failed after {:?}: Expected {} but got {}", part, result.name, duration.unwrap_or_default(), result.expected, actual.as_deref().unwrap_or("nothing")).on_red());
+            }
+        }
+    }
+
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        if let Some(e) = &result.error {
+            self.count(day, part, false);
+            print_error(e);
+            return;
+        }
+
+        if result.verdict == Verdict::Skipped {
+            self.skipped += 1;
+            println!("Part {} {}", part, "skipped, not implemented".yellow());
+            return;
+        }
+
+        let (Some(answer), Some(duration)) = (&result.answer, result.duration) else {
+            return;
+        };
+
+        match result.verdict {
+            Verdict::Unchecked => self.unchecked += 1,
+            _ => self.count(day, part, !result.regressed()),
+        }
+
+        let parse_duration = result.parse_duration.map(|d| format!("parse {:?}", d)).unwrap_or_default();
+        let alloc = result.alloc.map(|a| format!(" {:>36}", a.to_string())).unwrap_or_default();
+
+        // Multi-line answers don't fit in the row, so they follow below it for copying.
+        let (inline, block) = if answer.contains('\n') { ("see below", Some(answer)) } else { (answer.as_str(), None) };
+
+        let row = format!("Part {} output {:>12} {:>10} {:>16}{}", part, inline.blue(), format!("{:?}", duration).purple(), parse_duration.purple(), alloc.cyan()).on_blue();
+        let row = if result.cached { format!("{} {}", row, "cached".dimmed()) } else { row.to_string() };
+
+        match &result.verdict {
+            Verdict::Unchecked | Verdict::Skipped => println!("{}", row),
+            Verdict::Correct => println!("{} {}", row, "verified".on_bright_green()),
+            Verdict::Regressed { expected } => println!("{} {}", row, format!("regressed, expected {}", expected).on_red()),
+        }
+
+        if let Some(block) = block {
+            println!("{}", block);
+        }
+
+        if !result.variants.is_empty() {
+            print_variants(answer, duration, &result.variants);
+        }
+
+        for variant in &result.variants {
+            self.count(day, part, variant.agrees);
+        }
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        let mut failed_parts = self.failed.clone();
+        failed_parts.dedup();
+
+        let failed_parts = failed_parts.iter().map(|(day, part)| format!("Day{:0>2} part {}", day, part)).collect::<Vec<_>>();
+
+        let unchecked = if self.unchecked > 0 { format!(", {} unchecked", self.unchecked) } else { String::new() };
+        let skipped = if self.skipped > 0 { format!(", {} skipped", self.skipped) } else { String::new() };
+
+        if failed_parts.is_empty() {
+            println!("{}", format!("{} passed{}, 0 failed{}", self.passed, unchecked, skipped).green());
+        } else {
+            println!("{}", format!("{} passed{}, {} failed{}: {}", self.passed, unchecked, self.failed.len(), skipped, failed_parts.join(", ")).red());
+        }
+
+        Ok(())
+    }
+}
+
+/// Forwards every event to each of the contained reporters.
+pub struct Reporters(pub Vec<Box<dyn Reporter>>);
+
+impl Reporter for Reporters {
+    fn day_started(&mut self, day: u8, title: &str) {
+        self.0.iter_mut().for_each(|r| r.day_started(day, title));
+    }
+
+    fn test_finished(&mut self, day: u8, part: u8, result: &TestResult) {
+        self.0.iter_mut().for_each(|r| r.test_finished(day, part, result));
+    }
+
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        self.0.iter_mut().for_each(|r| r.part_finished(day, part, result));
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        self.0.iter_mut().try_for_each(|r| r.finish())
+    }
+}
+
+pub(crate) enum Event {
+    DayStarted(u8, String),
+    TestFinished(u8, u8, TestResult),
+    PartFinished(u8, u8, PartResult),
+}
+
+/// Buffers the events of a day run on a worker thread, so they can be reported later in day order.
+#[derive(Default)]
+pub(crate) struct Recorder {
+    events: Vec<Event>,
+}
+
+impl Recorder {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// The parts that were reported as finished so far.
+    pub(crate) fn finished_parts(&self) -> Vec<(u8, PartResult)> {
+        self.events.iter()
+            .filter_map(|event| match event {
+                Event::PartFinished(_, part, result) => Some((*part, result.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn replay(self, reporter: &mut dyn Reporter) {
+        for event in self.events {
+            match event {
+                Event::DayStarted(day, title) => reporter.day_started(day, &title),
+                Event::TestFinished(day, part, result) => reporter.test_finished(day, part, &result),
+                Event::PartFinished(day, part, result) => reporter.part_finished(day, part, &result),
+            }
+        }
+    }
+}
+
+impl Reporter for Recorder {
+    fn day_started(&mut self, day: u8, title: &str) {
+        self.events.push(Event::DayStarted(day, title.to_string()));
+    }
+
+    fn test_finished(&mut self, day: u8, part: u8, result: &TestResult) {
+        self.events.push(Event::TestFinished(day, part, result.clone()));
+    }
+
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        self.events.push(Event::PartFinished(day, part, result.clone()));
+    }
+}
+
+/// Prints the answers and times of the variants next to those of the part itself.
+fn print_variants(answer: &str, duration: Duration, variants: &[VariantResult]) {
+    println!("  {:<24} {:>12} {:>10} {:>9}", "solve", answer, format!("{:?}", duration), "1.00x");
+
+    for variant in variants {
+        let Some(variant_duration) = variant.duration else {
+            println!("  {:<24} {}", variant.name, variant.error.as_ref().map(|e| e.to_string()).unwrap_or_default().red());
+            continue;
+        };
+
+        let ratio = format!("{:.2}x", variant_duration.as_secs_f64() / duration.as_secs_f64().max(f64::MIN_POSITIVE));
+        let row = format!("  {:<24} {:>12} {:>10} {:>9}", variant.name, variant.answer.as_deref().unwrap_or_default(), format!("{:?}", variant_duration), ratio);
+
+        if variant.agrees {
+            println!("{}", row);
+        } else {
+            println!("{} {}", row, "disagrees".on_red());
+        }
+    }
+}
+
+pub(crate) fn print_error(e: &Error) {
+    let label = match e.kind() {
+        ErrorKind::Failed | ErrorKind::Panic | ErrorKind::MissingInput => "FAILED",
+        ErrorKind::Timeout(_) => "TIMEOUT",
+    };
+
+    println!("{}", format!("{} {}", label.bold(), e).on_red());
+}
+
+/// Writes every day and part as a single JSON document once the run finishes.
+/// A part whose actual input was not run only has its tests.
+pub struct JsonReporter {
+    path: String,
+    results: Vec<(u8, u8, PartResult)>,
+}
+
+impl JsonReporter {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string(), results: vec![] }
+    }
+
+    fn render(&self) -> String {
+        let days = group_by_day(&self.results)
+            .into_iter()
+            .map(|(day, parts)| {
+                let parts = parts.iter().map(|(part, result)| json_part(*part, result)).collect::<Vec<_>>();
+                format!("{{\"day\":{},\"parts\":[{}]}}", day, parts.join(","))
+            })
+            .collect::<Vec<_>>();
+
+        format!("{{\"days\":[{}]}}\n", days.join(","))
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        self.results.push((day, part, result.clone()));
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        write_report(&self.path, &self.render())
+    }
+}
+
+fn json_part(part: u8, result: &PartResult) -> String {
+    let tests = result.tests.iter().map(json_test).collect::<Vec<_>>();
+    let variants = result.variants.iter().map(json_variant).collect::<Vec<_>>();
+
+    if !result.ran_actual {
+        return format!("{{\"part\":{},\"tests\":[{}]}}", part, tests.join(","));
+    }
+
+    let (status, expected) = match (&result.error, &result.verdict) {
+        (Some(e), _) if matches!(e.kind(), ErrorKind::Timeout(_)) => ("timeout", None),
+        (Some(e), _) if e.kind() == ErrorKind::Panic => ("panic", None),
+        (Some(_), _) => ("error", None),
+        (None, Verdict::Unchecked) => ("unchecked", None),
+        (None, Verdict::Skipped) => ("skipped", None),
+        (None, Verdict::Correct) => ("correct", result.answer.as_deref()),
+        (None, Verdict::Regressed { expected }) => ("regressed", Some(expected.as_str())),
+    };
+
+    format!(
+        "{{\"part\":{},\"tests\":[{}],\"answer\":{},\"expected\":{},\"status\":\"{}\",\"parse_duration_ns\":{},\"duration_ns\":{},\"allocations\":{},\"allocated_bytes\":{},\"peak_bytes\":{},\"variants\":[{}],\"cached\":{},\"error\":{}}}",
+        part,
+        tests.join(","),
+        json_option(result.answer.as_deref()),
+        json_option(expected),
+        status,
+        json_duration(result.parse_duration),
+        json_duration(result.duration),
+        json_number(result.alloc.map(|a| a.count)),
+        json_number(result.alloc.map(|a| a.bytes)),
+        json_number(result.alloc.map(|a| a.peak)),
+        variants.join(","),
+        result.cached,
+        json_option(result.error.as_ref().map(|e| e.to_string()).as_deref()),
+    )
+}
+
+fn json_test(test: &TestResult) -> String {
+    format!(
+        "{{\"name\":{},\"passed\":{},\"skipped\":{},\"expected\":{},\"actual\":{},\"duration_ns\":{},\"error\":{}}}",
+        json_string(&test.name),
+        test.passed,
+        test.skipped,
+        json_string(&test.expected),
+        json_option(test.actual.as_deref()),
+        json_duration(test.duration),
+        json_option(test.error.as_ref().map(|e| e.to_string()).as_deref()),
+    )
+}
+
+fn json_variant(variant: &VariantResult) -> String {
+    format!(
+        "{{\"name\":{},\"answer\":{},\"agrees\":{},\"duration_ns\":{},\"error\":{}}}",
+        json_string(&variant.name),
+        json_option(variant.answer.as_deref()),
+        variant.agrees,
+        json_duration(variant.duration),
+        json_option(variant.error.as_ref().map(|e| e.to_string()).as_deref()),
+    )
+}
+
+fn json_duration(duration: Option<Duration>) -> String {
+    duration.map(|d| d.as_nanos().to_string()).unwrap_or("null".to_string())
+}
+
+fn json_number(n: Option<usize>) -> String {
+    n.map(|n| n.to_string()).unwrap_or("null".to_string())
+}
+
+fn json_option(s: Option<&str>) -> String {
+    s.map(json_string).unwrap_or("null".to_string())
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+
+    result
+}
+
+/// Writes a JUnit XML report with one test suite per day, one test case per example and one for the actual input if it was run.
+pub struct JunitReporter {
+    path: String,
+    results: Vec<(u8, u8, PartResult)>,
+}
+
+impl JunitReporter {
+    pub fn new(path: &str) -> Self {
+        Self { path: path.to_string(), results: vec![] }
+    }
+
+    fn render(&self) -> String {
+        let mut suites = String::new();
+        let (mut total_tests, mut total_failures, mut total_errors, mut total_time) = (0, 0, 0, 0.0);
+
+        for (day, parts) in group_by_day(&self.results) {
+            let mut cases = String::new();
+            let (mut tests, mut failures, mut errors, mut time) = (0, 0, 0, 0.0);
+
+            for (part, result) in parts {
+                let class_name = format!("Day{:0>2}.part{}", day, part);
+
+                for test in &result.tests {
+                    let body = match (&test.error, test.passed) {
+                        _ if test.skipped => "<skipped/>".to_string(),
+                        (Some(e), _) => {
+                            errors += 1;
+                            junit_error(e)
+                        }
+                        (None, false) => {
+                            failures += 1;
+                            junit_failure(&format!("expected {} but got {}", test.expected, test.actual.as_deref().unwrap_or("nothing")))
+                        }
+                        (None, true) => String::new(),
+                    };
+
+                    tests += 1;
+                    time += seconds(test.duration);
+                    cases.push_str(&junit_case(&class_name, &format!("test {}", test.name), test.duration, &body));
+                }
+
+                if !result.ran_actual {
+                    continue;
+                }
+
+                let body = match (&result.error, &result.verdict) {
+                    (Some(e), _) => {
+                        errors += 1;
+                        junit_error(e)
+                    }
+                    (None, Verdict::Regressed { expected }) => {
+                        failures += 1;
+                        junit_failure(&format!("expected {} but got {}", expected, result.answer.as_deref().unwrap_or("nothing")))
+                    }
+                    (None, Verdict::Skipped) => "<skipped/>".to_string(),
+                    (None, _) => result.answer.as_deref().map(|a| format!("<system-out>{}</system-out>", xml_escape(a))).unwrap_or_default(),
+                };
+
+                tests += 1;
+                time += seconds(result.duration);
+                cases.push_str(&junit_case(&class_name, "actual", result.duration, &body));
+
+                for variant in &result.variants {
+                    let body = match (&variant.error, variant.agrees) {
+                        (Some(e), _) => {
+                            errors += 1;
+                            junit_error(e)
+                        }
+                        (None, false) => {
+                            failures += 1;
+                            junit_failure(&format!("expected {} but got {}", result.answer.as_deref().unwrap_or("nothing"), variant.answer.as_deref().unwrap_or("nothing")))
+                        }
+                        (None, true) => String::new(),
+                    };
+
+                    tests += 1;
+                    time += seconds(variant.duration);
+                    cases.push_str(&junit_case(&class_name, &format!("variant {}", variant.name), variant.duration, &body));
+                }
+            }
+
+            writeln!(
+                suites,
+                "  <testsuite name=\"Day{:0>2}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">\n{}  </testsuite>",
+                day, tests, failures, errors, time, cases,
+            ).unwrap();
+
+            total_tests += tests;
+            total_failures += failures;
+            total_errors += errors;
+            total_time += time;
+        }
+
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"aoc-2023\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.6}\">\n{}</testsuites>\n",
+            total_tests, total_failures, total_errors, total_time, suites,
+        )
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        self.results.push((day, part, result.clone()));
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        write_report(&self.path, &self.render())
+    }
+}
+
+fn junit_case(class_name: &str, name: &str, duration: Option<Duration>, body: &str) -> String {
+    if body.is_empty() {
+        format!("    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"/>\n", class_name, xml_escape(name), seconds(duration))
+    } else {
+        format!("    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\">{}</testcase>\n", class_name, xml_escape(name), seconds(duration), body)
+    }
+}
+
+fn junit_failure(message: &str) -> String {
+    format!("<failure message=\"{}\"/>", xml_escape(message))
+}
+
+fn junit_error(e: &Error) -> String {
+    let kind = match e.kind() {
+        ErrorKind::Failed | ErrorKind::MissingInput => "error",
+        ErrorKind::Timeout(_) => "timeout",
+        ErrorKind::Panic => "panic",
+    };
+
+    format!("<error type=\"{}\" message=\"{}\"/>", kind, xml_escape(&e.to_string()))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn seconds(duration: Option<Duration>) -> f64 {
+    duration.map(|d| d.as_secs_f64()).unwrap_or(0.0)
+}
+
+/// How many of the slowest parts the summary lists.
+const SLOWEST_PARTS: usize = 5;
+
+/// Prints a table of every day with its answers, tests and times once the run finishes,
+/// and optionally writes the same summary as Markdown.
+pub struct SummaryReporter {
+    markdown: Option<String>,
+    titles: BTreeMap<u8, String>,
+    results: Vec<(u8, u8, PartResult)>,
+}
+
+impl SummaryReporter {
+    pub fn new(markdown: Option<&str>) -> Self {
+        Self { markdown: markdown.map(str::to_string), titles: BTreeMap::new(), results: vec![] }
+    }
+
+    /// Time spent solving both parts of each day on the actual input. Parsing is left out, like in the Time columns
+    /// and the slowest parts, so the shares of days and of parts are of the same total.
+    fn day_times(&self) -> Vec<(u8, Duration)> {
+        group_by_day(&self.results)
+            .into_iter()
+            .map(|(day, parts)| (day, parts.iter().filter_map(|(_, result)| result.duration).sum()))
+            .collect()
+    }
+
+    fn rows(&self, total: Duration) -> Vec<Vec<String>> {
+        let day_times = self.day_times();
+
+        group_by_day(&self.results)
+            .into_iter()
+            .zip(day_times)
+            .map(|((day, parts), (_, time))| {
+                let part = |id: u8| parts.iter().find(|(part, _)| *part == id).map(|(_, result)| *result);
+
+                vec![
+                    format!("{:0>2}", day),
+                    self.titles.get(&day).cloned().unwrap_or_default(),
+                    part(1).map(summary_answer).unwrap_or("-".to_string()),
+                    part(1).map(|result| format_duration(result.duration)).unwrap_or("-".to_string()),
+                    part(2).map(summary_answer).unwrap_or("-".to_string()),
+                    part(2).map(|result| format_duration(result.duration)).unwrap_or("-".to_string()),
+                    summary_tests(&parts),
+                    format!("{:.1}%", share(time, total)),
+                ]
+            })
+            .collect()
+    }
+
+    /// The slowest parts on the actual input, slowest first.
+    fn slowest(&self) -> Vec<(u8, u8, Duration)> {
+        let mut parts = self.results.iter()
+            .filter_map(|(day, part, result)| result.duration.map(|duration| (*day, *part, duration)))
+            .collect::<Vec<_>>();
+
+        parts.sort_by_key(|(_, _, duration)| Reverse(*duration));
+        parts.truncate(SLOWEST_PARTS);
+
+        parts
+    }
+
+    fn print(&self, total: Duration) {
+        let rows = self.rows(total);
+        let widths = (0..SUMMARY_HEADER.len())
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).chain([SUMMARY_HEADER[column].len()]).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        let line = |cells: Vec<String>| {
+            cells.iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| if column == 1 { format!("{:<width$}", cell) } else { format!("{:>width$}", cell) })
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+
+        println!();
+        println!("{}", line(SUMMARY_HEADER.iter().map(|s| s.to_string()).collect()).bold());
+        rows.into_iter().for_each(|row| println!("{}", line(row)));
+
+        println!("\n{} {:.2?} solving {} days, not counting parsing", "Total".bold(), total, self.titles.len());
+        println!("{}", "Slowest parts".bold());
+
+        for (day, part, duration) in self.slowest() {
+            println!("  Day{:0>2} part {} {:>12} {:>6.1}%", day, part, format!("{:.2?}", duration), share(duration, total));
+        }
+    }
+
+    fn render_markdown(&self, total: Duration) -> String {
+        let mut markdown = String::new();
+
+        writeln!(markdown, "| {} |", SUMMARY_HEADER.join(" | ")).unwrap();
+        writeln!(markdown, "|{}", (0..SUMMARY_HEADER.len()).map(|column| if column == 1 { " --- |" } else { " ---: |" }).collect::<String>()).unwrap();
+
+        for row in self.rows(total) {
+            writeln!(markdown, "| {} |", row.iter().map(|cell| markdown_escape(cell)).collect::<Vec<_>>().join(" | ")).unwrap();
+        }
+
+        writeln!(markdown, "\n**Total:** {:.2?} solving {} days, not counting parsing\n", total, self.titles.len()).unwrap();
+        writeln!(markdown, "Slowest parts:\n").unwrap();
+
+        for (day, part, duration) in self.slowest() {
+            writeln!(markdown, "1. Day{:0>2} part {}: {:.2?} ({:.1}%)", day, part, duration, share(duration, total)).unwrap();
+        }
+
+        markdown
+    }
+}
+
+impl Reporter for SummaryReporter {
+    fn day_started(&mut self, day: u8, title: &str) {
+        self.titles.insert(day, title.to_string());
+    }
+
+    fn part_finished(&mut self, day: u8, part: u8, result: &PartResult) {
+        self.results.push((day, part, result.clone()));
+    }
+
+    fn finish(&mut self) -> Result<(), Error> {
+        let total = self.day_times().into_iter().map(|(_, time)| time).sum::<Duration>();
+
+        self.print(total);
+
+        match &self.markdown {
+            Some(path) => {
+                write_report(path, &self.render_markdown(total))?;
+                println!("Summary written to {}", path);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+const SUMMARY_HEADER: [&str; 8] = ["Day", "Title", "Part 1", "Time", "Part 2", "Time", "Tests", "Share"];
+
+/// The answer of a part, or why it has none.
+fn summary_answer(result: &PartResult) -> String {
+    match (&result.error, &result.verdict, &result.answer) {
+        (Some(e), _, _) if matches!(e.kind(), ErrorKind::Timeout(_)) => "timeout".to_string(),
+        (Some(e), _, _) if e.kind() == ErrorKind::Panic => "panic".to_string(),
+        (Some(_), _, _) => "error".to_string(),
+        (None, Verdict::Skipped, _) => "skipped".to_string(),
+        (None, Verdict::Regressed { .. }, Some(answer)) => format!("{} (regressed)", summary_cell(answer)),
+        (None, _, Some(answer)) => summary_cell(answer),
+        (None, _, None) => "-".to_string(),
+    }
+}
+
+/// Multi-line answers do not fit in a table row, so only their size is shown.
+fn summary_cell(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("({} lines)", answer.lines().count())
+    } else {
+        answer.to_string()
+    }
+}
+
+/// Passed tests out of those that were not skipped, over both parts.
+fn summary_tests(parts: &[(u8, &PartResult)]) -> String {
+    let tests = parts.iter().flat_map(|(_, result)| &result.tests).filter(|test| !test.skipped);
+    let (passed, count) = tests.fold((0, 0), |(passed, count), test| (passed + test.passed as usize, count + 1));
+
+    if count == 0 {
+        "-".to_string()
+    } else {
+        format!("{}/{}", passed, count)
+    }
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    duration.map(|d| format!("{:.2?}", d)).unwrap_or("-".to_string())
+}
+
+fn share(duration: Duration, total: Duration) -> f64 {
+    100.0 * duration.as_secs_f64() / total.as_secs_f64().max(f64::MIN_POSITIVE)
+}
+
+fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn group_by_day(results: &[(u8, u8, PartResult)]) -> Vec<(u8, Vec<(u8, &PartResult)>)> {
+    let mut days: Vec<(u8, Vec<(u8, &PartResult)>)> = vec![];
+
+    for (day, part, result) in results {
+        match days.last_mut() {
+            Some((last_day, parts)) if last_day == day => parts.push((*part, result)),
+            _ => days.push((*day, vec![(*part, result)])),
+        }
+    }
+
+    days
+}
+
+fn write_report(path: &str, content: &str) -> Result<(), Error> {
+    fs::write(path, content).map_err(|e| Error::new(format!("could not write report: {}", e)).in_file(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+        assert_eq!(json_string("#..\n.#.\r\n\t"), r##""#..\n.#.\r\n\t""##);
+        assert_eq!(json_string("\u{1}\u{1f}é"), r#""\u0001\u001fé""#);
+    }
+
+    #[test]
+    fn escapes_xml() {
+        assert_eq!(xml_escape(r#"<a href="x">Tom & 'Jerry'</a>"#), "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;");
+    }
+
+    /// A regressed part with a passing example and a disagreeing variant, a part that panicked,
+    /// a part that is not implemented and a part whose actual input was not run.
+    fn results() -> Vec<(u8, u8, PartResult)> {
+        let test = TestResult {
+            name: "01_test1.txt".to_string(),
+            expected: "142".to_string(),
+            actual: Some("142".to_string()),
+            duration: Some(Duration::from_micros(5)),
+            error: None,
+            passed: true,
+            skipped: false,
+        };
+
+        let variant = VariantResult { name: "fast".to_string(), answer: Some("56".to_string()), duration: Some(Duration::from_millis(1)), error: None, agrees: false };
+
+        vec![
+            (1, 1, PartResult {
+                tests: vec![test],
+                duration: Some(Duration::from_millis(2)),
+                answer: Some("55".to_string()),
+                verdict: Verdict::Regressed { expected: "54".to_string() },
+                variants: vec![variant],
+                ran_actual: true,
+                ..PartResult::default()
+            }),
+            (1, 2, PartResult { error: Some(Error::panic(r#"boom "here""#).in_part(1, 2)), ran_actual: true, ..PartResult::default() }),
+            (2, 1, PartResult { verdict: Verdict::Skipped, ran_actual: true, ..PartResult::default() }),
+            (2, 2, PartResult::default()),
+        ]
+    }
+
+    #[test]
+    fn renders_json() {
+        let reporter = JsonReporter { path: String::new(), results: results() };
+
+        let expected = concat!(
+            r#"{"days":[{"day":1,"parts":["#,
+            r#"{"part":1,"tests":[{"name":"01_test1.txt","passed":true,"skipped":false,"expected":"142","actual":"142","duration_ns":5000,"error":null}],"#,
+            r#""answer":"55","expected":"54","status":"regressed","parse_duration_ns":null,"duration_ns":2000000,"allocations":null,"allocated_bytes":null,"peak_bytes":null,"#,
+            r#""variants":[{"name":"fast","answer":"56","agrees":false,"duration_ns":1000000,"error":null}],"cached":false,"error":null},"#,
+            r#"{"part":2,"tests":[],"answer":null,"expected":null,"status":"panic","parse_duration_ns":null,"duration_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null,"#,
+            r#""variants":[],"cached":false,"error":"Day01 part 2 boom \"here\""}]},"#,
+            r#"{"day":2,"parts":["#,
+            r#"{"part":1,"tests":[],"answer":null,"expected":null,"status":"skipped","parse_duration_ns":null,"duration_ns":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null,"#,
+            r#""variants":[],"cached":false,"error":null},"#,
+            r#"{"part":2,"tests":[]}]}]}"#,
+            "\n",
+        );
+
+        assert_eq!(reporter.render(), expected);
+    }
+
+    #[test]
+    fn renders_junit() {
+        let reporter = JunitReporter { path: String::new(), results: results() };
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="aoc-2023" tests="5" failures="2" errors="1" time="0.003005">
+  <testsuite name="Day01" tests="4" failures="2" errors="1" time="0.003005">
+    <testcase classname="Day01.part1" name="test 01_test1.txt" time="0.000005"/>
+    <testcase classname="Day01.part1" name="actual" time="0.002000"><failure message="expected 54 but got 55"/></testcase>
+    <testcase classname="Day01.part1" name="variant fast" time="0.001000"><failure message="expected 55 but got 56"/></testcase>
+    <testcase classname="Day01.part2" name="actual" time="0.000000"><error type="panic" message="Day01 part 2 boom &quot;here&quot;"/></testcase>
+  </testsuite>
+  <testsuite name="Day02" tests="1" failures="0" errors="0" time="0.000000">
+    <testcase classname="Day02.part1" name="actual" time="0.000000"><skipped/></testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+        assert_eq!(reporter.render(), expected);
+    }
+
+    #[test]
+    fn shares_days_and_parts_of_the_same_total() {
+        let solved = |millis, parse_millis| PartResult {
+            duration: Some(Duration::from_millis(millis)),
+            parse_duration: Some(Duration::from_millis(parse_millis)),
+            ran_actual: true,
+            ..PartResult::default()
+        };
+
+        let reporter = SummaryReporter { markdown: None, titles: BTreeMap::new(), results: vec![(1, 1, solved(1, 50)), (1, 2, solved(2, 50)), (2, 1, solved(1, 50))] };
+        let total = reporter.day_times().into_iter().map(|(_, time)| time).sum::<Duration>();
+
+        assert_eq!(total, Duration::from_millis(4));
+        assert_eq!(reporter.rows(total).iter().map(|row| row[7].as_str()).collect::<Vec<_>>(), ["75.0%", "25.0%"]);
+        assert_eq!(reporter.slowest().iter().map(|&(_, _, duration)| share(duration, total)).sum::<f64>(), 100.0);
+    }
+}
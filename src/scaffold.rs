@@ -0,0 +1,146 @@
+use std::fs;
+use std::path::Path;
+
+use crate::harness::{Context, Error, input_dir, input_path};
+
+const LIB_PATH: &str = "src/lib.rs";
+const DAYS_START: &str = "days![";
+const DAYS_END: &str = "];";
+
+const DAY_TEMPLATE: &str = "\
+use crate::harness::{Day, Error, NotImplemented, Part, Registration};
+
+pub const REGISTRATION: Registration = Registration::new({id}, \"{title}\", || day{nn}().f());
+
+fn day{nn}() -> Day<NotImplemented, NotImplemented> {
+    Day::new({id}, Box::new(Part1 {}), Box::new(Part2 {}))
+}
+
+pub struct Part1;
+
+impl Part<NotImplemented> for Part1 {
+    fn expect_test(&self) -> NotImplemented {
+        NotImplemented
+    }
+
+    fn solve(&self, _input: &[String]) -> Result<NotImplemented, Error> {
+        Ok(NotImplemented)
+    }
+}
+
+pub struct Part2;
+
+impl Part<NotImplemented> for Part2 {
+    fn expect_test(&self) -> NotImplemented {
+        NotImplemented
+    }
+
+    fn solve(&self, _input: &[String]) -> Result<NotImplemented, Error> {
+        Ok(NotImplemented)
+    }
+}
+";
+
+/// Generates the module of a new day with stub parts, empty example inputs, and registers it in `lib.rs`.
+/// Returns the paths that were created or changed.
+pub fn new_day(id: u8, title: &str) -> Result<Vec<String>, Error> {
+    let module_path = format!("src/day{:0>2}.rs", id);
+
+    if Path::new(&module_path).exists() {
+        return Err(Error::new(format!("Day{:0>2} already exists, refusing to overwrite it", id)).in_file(&module_path));
+    }
+
+    let lib = read(LIB_PATH)?;
+    let lib = register(&lib, id).map_err(|e| e.in_file(LIB_PATH))?;
+
+    let module = DAY_TEMPLATE
+        .replace("{id}", &id.to_string())
+        .replace("{nn}", &format!("{:0>2}", id))
+        .replace("{title}", &title.replace('\\', "\\\\").replace('"', "\\\""));
+
+    write(&module_path, &module)?;
+    write(LIB_PATH, &lib)?;
+
+    let mut changed = vec![module_path, LIB_PATH.to_string()];
+
+    fs::create_dir_all(input_dir()).map_err(|e| Error::new(format!("could not create input directory: {}", e)))?;
+
+    for test_index in 1..=2 {
+        let path = input_path(&format!("{:0>2}_test{}.txt", id, test_index));
+
+        if !Path::new(&path).exists() {
+            write(&path, "")?;
+            changed.push(path);
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Adds the day to the `days!` list in the source of `lib.rs`, keeping the list sorted.
+fn register(lib: &str, id: u8) -> Result<String, Error> {
+    let start = lib.find(DAYS_START).context("could not find the days! list")? + DAYS_START.len();
+    let end = start + lib[start..].find(DAYS_END).context("could not find the end of the days! list")?;
+
+    let module = format!("day{:0>2}", id);
+
+    let mut days = lib[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|day| !day.is_empty())
+        .collect::<Vec<_>>();
+
+    if days.contains(&module.as_str()) {
+        return Err(Error::new(format!("Day{:0>2} is already registered", id)));
+    }
+
+    days.push(&module);
+    days.sort();
+
+    let lines = days.chunks(10)
+        .map(|chunk| format!("    {},\n", chunk.join(", ")))
+        .collect::<String>();
+
+    Ok(format!("{}\n{}{}", &lib[..start], lines, &lib[end..]))
+}
+
+fn read(path: &str) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|e| Error::new(format!("could not read file: {}", e)).in_file(path))
+}
+
+fn write(path: &str, content: &str) -> Result<(), Error> {
+    fs::write(path, content).map_err(|e| Error::new(format!("could not write file: {}", e)).in_file(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "use crate::harness::Registry;\n\ndays![\n    day01, day02, day05,\n];\n\npub fn solve() {}\n";
+
+    #[test]
+    fn registers_a_day_in_order() {
+        let lib = register(LIB, 3).unwrap();
+
+        assert_eq!(lib, "use crate::harness::Registry;\n\ndays![\n    day01, day02, day03, day05,\n];\n\npub fn solve() {}\n");
+    }
+
+    #[test]
+    fn wraps_the_list_every_ten_days() {
+        let lib = (1..=10).fold(LIB.replace("day01, day02, day05,", ""), |lib, id| register(&lib, id).unwrap());
+        let lib = register(&lib, 11).unwrap();
+
+        assert!(lib.contains("days![\n    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10,\n    day11,\n];"), "{}", lib);
+    }
+
+    #[test]
+    fn refuses_a_registered_day() {
+        assert!(register(LIB, 5).is_err());
+    }
+
+    #[test]
+    fn requires_the_days_list() {
+        assert!(register("pub fn solve() {}\n", 3).is_err());
+        assert!(register("days![\n    day01,\n", 3).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Loading and saving the small text files the runner keeps in the input directory: the answers, the answer
+//! cache and the benchmark baseline.
+
+use std::fmt::Display;
+use std::fs;
+use std::io::ErrorKind;
+use std::str::FromStr;
+
+use crate::harness::{Context, Error};
+
+/// Reads and parses the file at `path`, or returns the default if it does not exist yet.
+/// `what` names the contents in the error if it can't be read.
+pub(crate) fn load<T: FromStr<Err = Error> + Default>(path: &str, what: &str) -> Result<T, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => s.parse::<T>().map_err(|e| e.in_file(path)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(Error::new(format!("could not read {}: {}", what, e)).in_file(path)),
+    }
+}
+
+pub(crate) fn save(value: &impl Display, path: &str, what: &str) -> Result<(), Error> {
+    fs::write(path, value.to_string()).map_err(|e| Error::new(format!("could not write {}: {}", what, e)).in_file(path))
+}
+
+/// Keeps a multi-line value on a single line by escaping backslashes and newlines, and the quote it is enclosed in if any.
+pub(crate) fn escape(value: &str, quote: Option<char>) -> String {
+    let mut result = String::new();
+
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if Some(c) == quote => {
+                result.push('\\');
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Reverses [escape], rejecting unknown escape sequences and a quote that is not escaped.
+pub(crate) fn unescape(value: &str, quote: Option<char>) -> Result<String, Error> {
+    let mut result = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next().context("unterminated escape sequence")? {
+                '\\' => result.push('\\'),
+                'n' => result.push('\n'),
+                c if Some(c) == quote => result.push(c),
+                _ => return Err(Error::new("invalid escape sequence")),
+            },
+            c if Some(c) == quote => return Err(Error::new(format!("unescaped {} in string", c))),
+            _ => result.push(c),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_backslashes_newlines_and_the_quote() {
+        assert_eq!(escape("a\\b\nc\"", None), "a\\\\b\\nc\"");
+        assert_eq!(escape("a\\b\nc\"", Some('"')), "a\\\\b\\nc\\\"");
+
+        for answer in ["", "42", "a\\nb", "#..#\n.##.\n", "\\\\\n\\", "say \"hi\""] {
+            assert_eq!(unescape(&escape(answer, None), None).unwrap(), answer);
+            assert_eq!(unescape(&escape(answer, Some('"')), Some('"')).unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_escapes() {
+        assert!(unescape("a\\", None).is_err());
+        assert!(unescape("a\\t", None).is_err());
+        assert!(unescape("a\\\"", None).is_err());
+        assert!(unescape("a\"b", Some('"')).is_err());
+    }
+}
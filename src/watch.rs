@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{stdout, Write};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use colored::Colorize;
+
+use crate::answers::{Answers, ANSWERS_FILE};
+use crate::harness::{input_dir, input_path, Registration, Reporter, RunOptions};
+use crate::report::PrettyReporter;
+use crate::worker;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and length of each watched file, `None` if it does not exist.
+type Snapshot = BTreeMap<String, Option<(SystemTime, u64)>>;
+
+/// Runs the day, then runs it again whenever one of its input files changes, until interrupted.
+/// Every run reads the inputs from disk again, so it sees the changes.
+pub fn watch(registration: Registration, options: &RunOptions) -> ! {
+    loop {
+        print!("\x1B[2J\x1B[H");
+        stdout().flush().ok();
+
+        let answers = Answers::load(&input_path(ANSWERS_FILE)).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            Answers::default()
+        });
+
+        let mut reporter = PrettyReporter::default();
+        worker::run_parallel(&[registration], &answers, options, None, &mut reporter);
+        reporter.finish().ok();
+
+        let files = watched_files(registration, options);
+        let before = snapshot(&files, registration.id);
+
+        println!("{}", format!("Watching {} files for changes, press Ctrl-C to stop", before.len()).dimmed());
+
+        while snapshot(&files, registration.id) == before {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// The inputs the day reads, including an overridden actual input.
+fn watched_files(registration: Registration, options: &RunOptions) -> Vec<String> {
+    let inputs = worker::catch_panic(|| Ok((registration.load().input_paths)())).unwrap_or_default();
+
+    inputs.into_iter()
+        .chain(options.input.clone())
+        .collect()
+}
+
+/// Takes a snapshot of the files and of every file in the input directory starting with the day's number,
+/// so creating a new test input such as `NN_test1.txt` counts as a change too.
+fn snapshot(files: &[String], id: u8) -> Snapshot {
+    let prefix = format!("{:0>2}", id);
+
+    let created =
+        fs::read_dir(input_dir())
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+            .map(|entry| input_path(&entry.file_name().to_string_lossy()));
+
+    files.iter()
+        .cloned()
+        .chain(created)
+        .map(|path| {
+            let metadata = fs::metadata(&path).ok().and_then(|m| Some((m.modified().ok()?, m.len())));
+            (path, metadata)
+        })
+        .collect()
+}
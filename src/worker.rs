@@ -0,0 +1,258 @@
+//! Threads that run the days and their parts: a pool of workers running days side by side, a solver
+//! thread per part so a slow part can be abandoned, and turning panics on either into errors.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::panic::{AssertUnwindSafe, PanicHookInfo};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::alloc;
+use crate::alloc::AllocStats;
+use crate::answers::Answers;
+use crate::harness::{Error, PartResult, Registration, Reporter, RunOptions, set_cancel_flag, timed};
+use crate::report::Recorder;
+
+/// Stack size for the threads solving a part, as several solutions recurse deeply.
+/// Overflowing it aborts the whole process, which neither the timeout nor [catch_panic] can prevent.
+const SOLVER_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+pub struct ParallelRun {
+    pub results: Vec<(u8, Vec<(u8, PartResult)>)>,
+    /// Time from the first day starting to the last day finishing.
+    pub wall_time: Duration,
+    /// CPU time the process used on all of its threads during the run, where the platform reports it, which is only Linux.
+    pub cpu_time: Option<Duration>,
+}
+
+impl ParallelRun {
+    /// The CPU time as printed, as `<10ms` when it is below the resolution it is measured in.
+    pub fn cpu_time_display(&self) -> Option<String> {
+        let resolution = Duration::from_millis(1000 / CLOCK_TICKS_PER_SECOND);
+
+        self.cpu_time.map(|cpu_time| if cpu_time < resolution { format!("<{:?}", resolution) } else { format!("{:?}", cpu_time) })
+    }
+}
+
+/// Runs the days on a pool of `jobs` worker threads, or one per core if not given. Each day is loaded on its worker,
+/// and its events are buffered and reported as soon as all days before it are done.
+pub fn run_parallel(days: &[Registration], answers: &Answers, options: &RunOptions, jobs: Option<NonZeroUsize>, reporter: &mut dyn Reporter) -> ParallelRun {
+    let workers = jobs.or_else(|| thread::available_parallelism().ok()).map_or(1, NonZeroUsize::get).min(days.len());
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    let mut results = vec![];
+    let cpu_start = process_cpu_time();
+
+    let ((), wall_time) = timed(|| thread::scope(|scope| {
+        for _ in 0..workers {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                while let Some(registration) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let mut recorder = Recorder::default();
+                    let day_results = run_isolated(registration, answers, options, &mut recorder);
+
+                    if sender.send((registration.id, recorder, day_results)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+
+        for (id, recorder, day_results) in receiver {
+            pending.insert(id, (recorder, day_results));
+
+            while let Some((recorder, day_results)) = days.get(results.len()).and_then(|r| pending.remove(&r.id)) {
+                recorder.replay(reporter);
+                results.push((days[results.len()].id, day_results));
+            }
+        }
+    }));
+
+    let cpu_time = process_cpu_time().zip(cpu_start).map(|(end, start)| end.saturating_sub(start));
+
+    ParallelRun { results, wall_time, cpu_time }
+}
+
+/// Unit of the times in `/proc/self/stat`, which Linux keeps at 100 per second whatever its own tick rate.
+const CLOCK_TICKS_PER_SECOND: u64 = 100;
+
+/// CPU time the process has used so far in user and kernel mode, read from `/proc/self/stat` where it exists.
+fn process_cpu_time() -> Option<Duration> {
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+
+    // The command name is in parentheses and may contain spaces, the fields after it start with the state.
+    let fields = stat.rsplit_once(')')?.1.split_whitespace().collect::<Vec<_>>();
+    let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+
+    Some(Duration::from_millis(ticks * 1000 / CLOCK_TICKS_PER_SECOND))
+}
+
+/// Runs a day, turning a panic outside of its parts, such as while loading the day, into a failure of every
+/// selected part that has not finished yet.
+fn run_isolated(registration: &Registration, answers: &Answers, options: &RunOptions, recorder: &mut Recorder) -> Vec<(u8, PartResult)> {
+    let e = match catch_panic(|| Ok(registration.load().run(answers, options, recorder))) {
+        Ok(results) => return results,
+        Err(e) => e,
+    };
+
+    if recorder.is_empty() {
+        recorder.day_started(registration.id, registration.title);
+    }
+
+    let finished = recorder.finished_parts();
+
+    let mut results = finished.clone();
+
+    for part in [1, 2].into_iter().filter(|part| options.part.is_none_or(|p| p == *part) && finished.iter().all(|(p, _)| p != part)) {
+        let result = PartResult { error: Some(e.clone().in_part(registration.id, part)), ran_actual: true, ..PartResult::default() };
+        recorder.part_finished(registration.id, part, &result);
+        results.push((part, result));
+    }
+
+    results
+}
+
+/// Runs `f` on its own thread, which is abandoned if it does not finish within `timeout`, and returns its result
+/// with how long it took. Running it on its own thread also keeps its allocations apart from those of other parts.
+///
+/// Threads can't be stopped from outside. Once abandoned, [crate::harness::check_cancelled] fails on the thread, so a part that calls
+/// it in its long loops stops soon after. One that doesn't keeps running, using a core while other days are timed
+/// and piling up with every rerun in `watch`, until it finishes on its own or the process exits.
+pub fn run_on_solver_thread<R: Send + 'static>(
+    name: String,
+    timeout: Duration,
+    f: impl FnOnce() -> Result<R, Error> + Send + 'static,
+) -> Result<(R, Duration, Option<AllocStats>), Error> {
+    let (sender, receiver) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let start = Instant::now();
+
+    let flag = cancelled.clone();
+
+    thread::Builder::new()
+        .name(name)
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || {
+            set_cancel_flag(flag);
+            // Fails only if the part timed out and nobody is waiting for the result anymore.
+            let _ = sender.send(alloc::measure(|| timed(|| catch_panic(f))));
+        })
+        .map_err(|e| Error::new(format!("could not start solver thread: {}", e)))?;
+
+    match receiver.recv_timeout(timeout) {
+        Ok(((result, duration), alloc)) => result.map(|actual| (actual, duration, alloc)),
+        Err(RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::Relaxed);
+            Err(Error::timeout(start.elapsed()))
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(Error::new("solver thread stopped without a result")),
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are being caught by [catch_panic].
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Message and location of the last panic caught on this thread.
+    static CAUGHT: Cell<Option<String>> = const { Cell::new(None) };
+}
+
+static PANIC_HOOK: Once = Once::new();
+
+/// Runs `f`, turning a panic into an error with the panic's message and location.
+/// Panics caught this way are reported as errors instead of being printed by the default hook.
+pub fn catch_panic<R>(f: impl FnOnce() -> Result<R, Error>) -> Result<R, Error> {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                CAUGHT.set(Some(describe_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.unwrap_or_else(|_| Err(Error::panic(CAUGHT.take().unwrap_or("panicked".to_string()))))
+}
+
+fn describe_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+
+    let message = payload.downcast_ref::<&str>()
+        .copied()
+        .or(payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => format!("panicked at {}: {}", location, message),
+        None => format!("panicked: {}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::{check_cancelled, ErrorKind};
+
+    fn with_cpu_time(cpu_time: Option<Duration>) -> ParallelRun {
+        ParallelRun { results: vec![], wall_time: Duration::ZERO, cpu_time }
+    }
+
+    #[test]
+    fn shows_cpu_time_below_one_tick_as_below_the_resolution() {
+        assert_eq!(with_cpu_time(Some(Duration::ZERO)).cpu_time_display().as_deref(), Some("<10ms"));
+        assert_eq!(with_cpu_time(Some(Duration::from_millis(120))).cpu_time_display().as_deref(), Some("120ms"));
+        assert_eq!(with_cpu_time(None).cpu_time_display(), None);
+    }
+
+    #[test]
+    fn cancels_a_part_that_times_out() {
+        let (sender, receiver) = mpsc::channel();
+
+        let result = run_on_solver_thread("cancelled".to_string(), Duration::from_millis(50), move || {
+            let stopped = loop {
+                if let Err(e) = check_cancelled() {
+                    break e;
+                }
+
+                thread::sleep(Duration::from_millis(1));
+            };
+
+            sender.send(stopped.to_string()).unwrap();
+            Err::<(), _>(stopped)
+        });
+
+        assert!(matches!(result.unwrap_err().kind(), ErrorKind::Timeout(_)));
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), "cancelled after timing out");
+    }
+
+    #[test]
+    fn returns_the_result_of_a_part_within_its_timeout() {
+        let (answer, _, _) = run_on_solver_thread("finished".to_string(), Duration::from_secs(5), || {
+            check_cancelled()?;
+            Ok(42)
+        }).unwrap();
+
+        assert_eq!(answer, 42);
+    }
+}